@@ -0,0 +1,8 @@
+//! Messages for operators about problems the server recovers from. They go to stderr, as
+//! stdout carries the JSON-RPC stream.
+
+use std::fmt;
+
+pub fn report(message: impl fmt::Display) {
+    eprintln!("{}", message);
+}
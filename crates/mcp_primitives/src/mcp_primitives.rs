@@ -1 +1,7 @@
+pub mod diagnostics;
+pub mod plugins;
+pub mod prompts;
+pub mod resources;
+pub mod telemetry;
 pub mod tools;
+pub mod units;
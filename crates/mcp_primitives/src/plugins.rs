@@ -0,0 +1,226 @@
+//! Extra unit providers that run as separate executables.
+//!
+//! Every file in the plugins directory is started once per request with a single JSON
+//! object on stdin and must print a single JSON object on stdout:
+//!
+//! - `{"method": "list_units"}` answers `{"result": {"units": [{"name": "...", "symbol":
+//!   "...", "aliases": ["..."], "category": "...", "description": "..."}]}}`
+//! - `{"method": "convert", "params": {"value": 1.0, "from_unit": "...", "to_unit": "..."}}`
+//!   answers `{"result": {"value": 2.0}}`
+//!
+//! Either may answer `{"error": "message"}` instead. Units are matched by name, symbol or
+//! alias, and built-in units always take precedence over plugin ones.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use tokio::{io::AsyncWriteExt, process::Command};
+
+use crate::diagnostics;
+
+/// How long a plugin may take to answer before the request is abandoned.
+const PLUGIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A unit as declared by a plugin's `list_units` answer.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PluginUnit {
+    pub name: String,
+    #[serde(default)]
+    pub symbol: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub category: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl PluginUnit {
+    fn matches(&self, unit: &str) -> bool {
+        let unit_lower = unit.to_lowercase();
+        self.symbol.as_deref() == Some(unit)
+            || self.name.to_lowercase() == unit_lower
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.to_lowercase() == unit_lower)
+    }
+}
+
+#[derive(Deserialize)]
+struct ListUnitsResult {
+    units: Vec<PluginUnit>,
+}
+
+#[derive(Deserialize)]
+struct ConvertResult {
+    value: f64,
+}
+
+/// One plugin executable and the units it announced at discovery.
+pub struct Plugin {
+    name: String,
+    path: PathBuf,
+    units: Vec<PluginUnit>,
+}
+
+impl Plugin {
+    /// Starts the plugin, announcing its units through `list_units`.
+    pub async fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let mut plugin = Self {
+            name,
+            path,
+            units: Vec::new(),
+        };
+        let ListUnitsResult { units } = plugin.call(json!({ "method": "list_units" })).await?;
+        plugin.units = units;
+        Ok(plugin)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn units(&self) -> &[PluginUnit] {
+        &self.units
+    }
+
+    async fn call<T: DeserializeOwned>(&self, request: Value) -> Result<T> {
+        let mut child = Command::new(&self.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start plugin {}", self.path.display()))?;
+
+        let exchange = async {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(request.to_string().as_bytes()).await?;
+                stdin.write_all(b"\n").await?;
+            }
+            child.wait_with_output().await
+        };
+        let output = tokio::time::timeout(PLUGIN_TIMEOUT, exchange)
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "Plugin {} did not answer within {} seconds",
+                    self.name,
+                    PLUGIN_TIMEOUT.as_secs()
+                )
+            })??;
+
+        let response: Value = serde_json::from_slice(&output.stdout)
+            .with_context(|| format!("Plugin {} returned invalid JSON", self.name))?;
+        if let Some(error) = response.get("error") {
+            return Err(anyhow!(
+                "Plugin {}: {}",
+                self.name,
+                error
+                    .as_str()
+                    .map_or_else(|| error.to_string(), str::to_string)
+            ));
+        }
+
+        serde_json::from_value(response["result"].clone())
+            .with_context(|| format!("Plugin {} returned an unexpected result", self.name))
+    }
+}
+
+/// The plugins discovered at startup.
+#[derive(Default)]
+pub struct Plugins {
+    plugins: Vec<Plugin>,
+}
+
+impl Plugins {
+    /// Loads every executable in `dir`. Plugins that fail to start or to list their units
+    /// are reported on stderr and skipped so one broken provider cannot stop the server.
+    pub async fn discover(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read plugins directory {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && !path
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            })
+            .collect();
+        paths.sort();
+
+        let mut plugins = Vec::new();
+        for path in paths {
+            match Plugin::load(&path).await {
+                Ok(plugin) => plugins.push(plugin),
+                Err(error) => diagnostics::report(format_args!(
+                    "Skipping plugin {}: {:#}",
+                    path.display(),
+                    error
+                )),
+            }
+        }
+
+        Ok(Self { plugins })
+    }
+
+    /// Every plugin unit with the plugin that provides it, in discovery order.
+    pub fn units(&self) -> impl Iterator<Item = (&Plugin, &PluginUnit)> {
+        self.plugins
+            .iter()
+            .flat_map(|plugin| plugin.units.iter().map(move |unit| (plugin, unit)))
+    }
+
+    pub fn find(&self, unit: &str) -> Option<(&Plugin, &PluginUnit)> {
+        self.units()
+            .find(|(_, plugin_unit)| plugin_unit.matches(unit))
+    }
+
+    /// Categories contributed by plugins, without duplicates.
+    pub fn categories(&self) -> Vec<&str> {
+        let mut categories: Vec<&str> = self
+            .units()
+            .map(|(_, unit)| unit.category.as_str())
+            .collect();
+        categories.sort_unstable();
+        categories.dedup();
+        categories
+    }
+
+    /// Converts between two units of the same plugin category.
+    pub async fn convert(&self, value: f64, from_unit: &str, to_unit: &str) -> Result<f64> {
+        let (plugin, from) = self
+            .find(from_unit)
+            .ok_or_else(|| anyhow!("Unsupported unit: {}", from_unit))?;
+        let to = plugin
+            .units
+            .iter()
+            .find(|unit| unit.matches(to_unit) && unit.category == from.category)
+            .ok_or_else(|| anyhow!("Unsupported unit: {} for type: {}", to_unit, from.category))?;
+
+        let ConvertResult { value } = plugin
+            .call(json!({
+                "method": "convert",
+                "params": {
+                    "value": value,
+                    "from_unit": from.name,
+                    "to_unit": to.name
+                }
+            }))
+            .await?;
+        Ok(value)
+    }
+}
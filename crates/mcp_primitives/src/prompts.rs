@@ -0,0 +1,290 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use context_server::{Prompt, PromptArgument, PromptContent, PromptExecutor, PromptMessage, Role};
+
+use crate::units::{self, Conversion, ConversionContext, UnitDefinition, UnitType};
+
+#[derive(Clone, Copy, Debug)]
+enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            other => Err(anyhow!(
+                "Unsupported difficulty: {} (expected easy, medium or hard)",
+                other
+            )),
+        }
+    }
+
+    /// Easy problems stay within plain scale factors; temperature offsets come in at medium.
+    /// Decibel scales are left out: large quiz values overflow them, and levels such as dBm
+    /// depend on a reference power the problem would have to state.
+    fn allows(self, def: &UnitDefinition) -> bool {
+        match def.conversion {
+            Conversion::Linear(_) => true,
+            Conversion::Contextual(_) | Conversion::Logarithmic { .. } => false,
+            Conversion::Affine { .. } => !matches!(self, Difficulty::Easy),
+            Conversion::Custom { .. } | Conversion::Scripted { .. } => false,
+        }
+    }
+
+    fn value(self, rng: &mut Rng) -> f64 {
+        match self {
+            Difficulty::Easy => rng.range(1, 20) as f64,
+            Difficulty::Medium => rng.range(1, 500) as f64,
+            Difficulty::Hard => rng.range(1, 100_000) as f64 / 10.0,
+        }
+    }
+
+    const fn decimals(self) -> usize {
+        match self {
+            Difficulty::Easy | Difficulty::Medium => 2,
+            Difficulty::Hard => 4,
+        }
+    }
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "easy"),
+            Difficulty::Medium => write!(f, "medium"),
+            Difficulty::Hard => write!(f, "hard"),
+        }
+    }
+}
+
+/// Small xorshift generator so quizzes can be reproduced from a seed.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform integer in `low..=high`.
+    fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low + 1)
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() as u64 - 1) as usize]
+    }
+}
+
+struct Problem {
+    value: f64,
+    from: &'static UnitDefinition,
+    to: &'static UnitDefinition,
+    answer: f64,
+}
+
+pub struct ConversionQuiz;
+
+impl Default for ConversionQuiz {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConversionQuiz {
+    pub const fn new() -> Self {
+        Self
+    }
+
+    fn generate(
+        difficulty: Difficulty,
+        count: usize,
+        unit_type: Option<UnitType>,
+        rng: &mut Rng,
+    ) -> Result<Vec<Problem>> {
+        let categories = match unit_type {
+            Some(unit_type) => vec![unit_type],
+            None => units::categories(),
+        };
+
+        let pools: Vec<Vec<&'static UnitDefinition>> = categories
+            .into_iter()
+            .map(|unit_type| {
+                units::units_of(unit_type)
+                    .filter(|def| difficulty.allows(def))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|pool| pool.len() > 1)
+            .collect();
+
+        if pools.is_empty() {
+            return Err(anyhow!(
+                "No {} problems available for the requested category",
+                difficulty
+            ));
+        }
+
+        Ok((0..count)
+            .map(|_| {
+                let pool = rng.pick(&pools);
+                let from = *rng.pick(pool);
+                let to = loop {
+                    let to = *rng.pick(pool);
+                    if to.name != from.name {
+                        break to;
+                    }
+                };
+
+                let value = difficulty.value(rng);
+                let context = ConversionContext::default();
+                let answer = to
+                    .conversion
+                    .from_base(from.conversion.to_base(value, &context), &context);
+
+                Problem {
+                    value,
+                    from,
+                    to,
+                    answer,
+                }
+            })
+            .collect())
+    }
+
+    fn render(difficulty: Difficulty, seed: u64, problems: &[Problem]) -> String {
+        let mut text = format!(
+            "Here are {} unit conversion practice problems ({} difficulty, seed {}). Present the problems to the learner, wait for their answers, then check them against the answer key. Round answers to {} decimal places.\n\nProblems:\n",
+            problems.len(),
+            difficulty,
+            seed,
+            difficulty.decimals()
+        );
+
+        for (index, problem) in problems.iter().enumerate() {
+            text.push_str(&format!(
+                "{}. Convert {} {} to {}.\n",
+                index + 1,
+                problem.value,
+                problem.from.name.replace('_', " "),
+                problem.to.name.replace('_', " ")
+            ));
+        }
+
+        text.push_str("\nAnswer key:\n");
+        for (index, problem) in problems.iter().enumerate() {
+            text.push_str(&format!(
+                "{}. {:.*} {}\n",
+                index + 1,
+                difficulty.decimals(),
+                problem.answer,
+                problem.to.name.replace('_', " ")
+            ));
+        }
+
+        text
+    }
+}
+
+#[async_trait]
+impl PromptExecutor for ConversionQuiz {
+    fn name(&self) -> &str {
+        "conversion_quiz"
+    }
+
+    async fn compute(
+        &self,
+        arguments: Option<HashMap<String, String>>,
+    ) -> Result<Vec<PromptMessage>> {
+        let arguments = arguments.unwrap_or_default();
+
+        let difficulty = match arguments.get("difficulty") {
+            Some(difficulty) => Difficulty::parse(difficulty)?,
+            None => Difficulty::Medium,
+        };
+
+        let count = match arguments.get("count") {
+            Some(count) => count
+                .parse::<usize>()
+                .ok()
+                .filter(|count| (1..=50).contains(count))
+                .ok_or_else(|| anyhow!("Invalid count: {} (expected 1 to 50)", count))?,
+            None => 5,
+        };
+
+        let unit_type = match arguments.get("category") {
+            Some(category) => Some(category.parse::<UnitType>()?),
+            None => None,
+        };
+
+        let seed = match arguments.get("seed") {
+            Some(seed) => seed
+                .parse::<u64>()
+                .map_err(|_| anyhow!("Invalid seed: {} (expected an integer)", seed))?,
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_nanos() as u64)
+                .unwrap_or_default(),
+        };
+
+        let problems = Self::generate(difficulty, count, unit_type, &mut Rng::new(seed))?;
+
+        Ok(vec![PromptMessage {
+            role: Role::User,
+            content: PromptContent::Text {
+                text: Self::render(difficulty, seed, &problems),
+            },
+        }])
+    }
+
+    fn to_prompt(&self) -> Prompt {
+        Prompt {
+            name: self.name().to_string(),
+            description: Some("Generate unit conversion practice problems with an answer key, for tutoring and self-study.".to_string()),
+            arguments: vec![
+                PromptArgument {
+                    name: "difficulty".to_string(),
+                    description: Some("easy (whole numbers, simple scale factors), medium (adds temperature scales) or hard (decimal values, four decimal places). Defaults to medium.".to_string()),
+                    required: Some(false),
+                },
+                PromptArgument {
+                    name: "count".to_string(),
+                    description: Some("Number of problems to generate, from 1 to 50. Defaults to 5.".to_string()),
+                    required: Some(false),
+                },
+                PromptArgument {
+                    name: "category".to_string(),
+                    description: Some(format!(
+                        "Restrict problems to one category ({}). Defaults to a mix of all categories.",
+                        units::categories()
+                            .iter()
+                            .map(|unit_type| unit_type.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )),
+                    required: Some(false),
+                },
+                PromptArgument {
+                    name: "seed".to_string(),
+                    description: Some("Integer seed to reproduce a previous quiz.".to_string()),
+                    required: Some(false),
+                },
+            ],
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use context_server::{Resource, ResourceContent, ResourceExecutor};
+
+use crate::units::{self, UnitType};
+
+/// Markdown explainer for one unit category, served at `docs://<category>`.
+pub struct CategoryDocumentation {
+    unit_type: UnitType,
+}
+
+impl CategoryDocumentation {
+    pub const fn new(unit_type: UnitType) -> Self {
+        Self { unit_type }
+    }
+
+    /// One documentation resource per supported category.
+    pub fn all() -> Vec<Self> {
+        units::categories().into_iter().map(Self::new).collect()
+    }
+
+    fn uri(&self) -> String {
+        format!("docs://{}", self.unit_type)
+    }
+
+    fn render(&self) -> String {
+        let mut markdown = format!(
+            "# {} units\n\n{}\n\nAll conversions go through the base unit, {}.\n\n| Unit | Symbol | Aliases | Conversion |\n| --- | --- | --- | --- |\n",
+            self.unit_type.label(),
+            self.unit_type.description(),
+            self.unit_type.base_unit()
+        );
+
+        for def in units::units_of(self.unit_type) {
+            markdown.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                def.name,
+                def.symbol,
+                def.aliases.join(", "),
+                def.conversion_summary()
+            ));
+        }
+
+        for def in units::units_of(self.unit_type) {
+            markdown.push_str(&format!(
+                "\n## {} ({})\n\n{}\n",
+                def.name, def.symbol, def.description
+            ));
+        }
+
+        markdown
+    }
+}
+
+#[async_trait]
+impl ResourceExecutor for CategoryDocumentation {
+    async fn read(&self) -> Result<Vec<ResourceContent>> {
+        Ok(vec![ResourceContent {
+            uri: self.uri(),
+            mime_type: Some("text/markdown".to_string()),
+            text: Some(self.render()),
+            blob: None,
+        }])
+    }
+
+    fn to_resource(&self) -> Resource {
+        Resource {
+            uri: self.uri(),
+            name: format!("{} units", self.unit_type.label()),
+            description: Some(format!(
+                "Origin and typical use of every supported {} unit, with conversion factors to {}.",
+                self.unit_type,
+                self.unit_type.base_unit()
+            )),
+            mime_type: Some("text/markdown".to_string()),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+
+/// Shortest time between two rewrites of the stats file; counts recorded in between are
+/// written by the next rewrite or by [`Telemetry::flush`].
+const WRITE_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Default)]
+struct Counts {
+    category_pairs: BTreeMap<String, u64>,
+    /// Whether counts changed since the file was last written.
+    dirty: bool,
+    last_write: Option<Instant>,
+}
+
+/// Opt-in tally of which category pairs get converted, kept in a local JSON file.
+///
+/// Only category names are recorded (never values or the unit strings callers sent), and
+/// nothing leaves the machine; operators read the file to decide which categories to add.
+pub struct Telemetry {
+    path: PathBuf,
+    counts: Mutex<Counts>,
+    /// Held while writing so snapshots reach the file in the order they were taken.
+    writing: tokio::sync::Mutex<()>,
+}
+
+impl Telemetry {
+    /// Opens the stats file at `path`, continuing the counts already in it.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let category_pairs = if path.exists() {
+            Self::load(&path)
+                .with_context(|| format!("Invalid telemetry file {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            path,
+            counts: Mutex::new(Counts {
+                category_pairs,
+                ..Counts::default()
+            }),
+            writing: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    fn load(path: &Path) -> Result<BTreeMap<String, u64>> {
+        let stats: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        Ok(stats["category_pairs"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(pair, count)| Some((pair.clone(), count.as_u64()?)))
+            .collect())
+    }
+
+    /// Counts one conversion per `(from, to)` category pair, rewriting the stats file at
+    /// most once every [`WRITE_INTERVAL`].
+    pub async fn record(&self, pairs: impl IntoIterator<Item = (String, String)>) -> Result<()> {
+        {
+            let mut counts = self.lock_counts();
+            for (from, to) in pairs {
+                *counts
+                    .category_pairs
+                    .entry(format!("{} -> {}", from, to))
+                    .or_default() += 1;
+            }
+            counts.dirty = true;
+            if counts
+                .last_write
+                .is_some_and(|last_write| last_write.elapsed() < WRITE_INTERVAL)
+            {
+                return Ok(());
+            }
+        }
+        self.flush().await
+    }
+
+    /// Writes counts not yet in the stats file, as when the server shuts down.
+    pub async fn flush(&self) -> Result<()> {
+        let _writing = self.writing.lock().await;
+        let stats = {
+            let mut counts = self.lock_counts();
+            if !counts.dirty {
+                return Ok(());
+            }
+            counts.dirty = false;
+            counts.last_write = Some(Instant::now());
+            serde_json::to_string_pretty(&json!({ "category_pairs": counts.category_pairs }))?
+        };
+
+        tokio::fs::write(&self.path, stats)
+            .await
+            .with_context(|| format!("Failed to write telemetry file {}", self.path.display()))
+    }
+
+    fn lock_counts(&self) -> std::sync::MutexGuard<'_, Counts> {
+        self.counts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use context_server::{Tool, ToolContent, ToolExecutor};
+use schemars::{JsonSchema, schema_for};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::{AnnotatedTool, ToolAnnotations, UnitConversion, unit_conversion::UnitConversionItem};
+
+struct HistoryEntry {
+    id: u64,
+    timestamp: u64,
+    item: UnitConversionItem,
+    result: Value,
+}
+
+struct HistoryState {
+    next_id: u64,
+    entries: VecDeque<HistoryEntry>,
+}
+
+/// Conversions performed during this session, shared by unit_conversion, which records
+/// them, and conversion_history, which reads and replays them. Only the most recent
+/// `capacity` entries are kept.
+pub struct History {
+    capacity: usize,
+    state: Mutex<HistoryState>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl History {
+    pub const fn new() -> Self {
+        Self::with_capacity(100)
+    }
+
+    pub const fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(HistoryState {
+                next_id: 1,
+                entries: VecDeque::new(),
+            }),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, HistoryState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn record(&self, item: &UnitConversionItem, result: &Value) {
+        let mut state = self.state();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.entries.push_back(HistoryEntry {
+            id,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+            item: item.clone(),
+            result: result.clone(),
+        });
+        while state.entries.len() > self.capacity {
+            state.entries.pop_front();
+        }
+    }
+
+    /// Entries newest first.
+    fn list(&self) -> Vec<Value> {
+        self.state()
+            .entries
+            .iter()
+            .rev()
+            .map(|entry| {
+                json!({
+                    "id": entry.id,
+                    "timestamp": entry.timestamp,
+                    "from_unit": entry.item.from_unit,
+                    "to_unit": entry.item.to_unit,
+                    "result": entry.result
+                })
+            })
+            .collect()
+    }
+
+    /// The request behind entry `id`, or behind the latest entry.
+    fn find(&self, id: Option<u64>) -> Option<UnitConversionItem> {
+        let state = self.state();
+        match id {
+            Some(id) => state.entries.iter().find(|entry| entry.id == id),
+            None => state.entries.back(),
+        }
+        .map(|entry| entry.item.clone())
+    }
+
+    fn clear(&self) -> usize {
+        let mut state = self.state();
+        let cleared = state.entries.len();
+        state.entries.clear();
+        cleared
+    }
+}
+
+#[derive(Deserialize, JsonSchema, Serialize)]
+struct ConversionHistoryParams {
+    #[schemars(
+        description = "What to do: \"list\" recent conversions (newest first), \"repeat\" a previous conversion with a new value, or \"clear\" the history"
+    )]
+    action: String,
+    #[schemars(description = "For repeat: the new value to convert")]
+    value: Option<f64>,
+    #[schemars(
+        description = "For repeat: id of the conversion to repeat, as shown by list (default: the most recent)"
+    )]
+    id: Option<u64>,
+    #[schemars(
+        description = "For list: maximum number of entries to return (default 10, at most 200)"
+    )]
+    limit: Option<usize>,
+    #[schemars(
+        description = "For list: number of entries to skip, taken from next_offset of a previous page"
+    )]
+    offset: Option<usize>,
+}
+
+pub struct ConversionHistory {
+    history: Arc<History>,
+    unit_conversion: Arc<UnitConversion>,
+}
+
+impl ConversionHistory {
+    /// `unit_conversion` should record into `history` so that repeats show up in the list.
+    pub const fn new(history: Arc<History>, unit_conversion: Arc<UnitConversion>) -> Self {
+        Self {
+            history,
+            unit_conversion,
+        }
+    }
+}
+
+impl AnnotatedTool for ConversionHistory {
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations {
+            title: "Conversion History".to_string(),
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: false,
+            open_world_hint: false,
+        }
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for ConversionHistory {
+    async fn execute(&self, arguments: Option<Value>) -> Result<Vec<ToolContent>> {
+        let params: ConversionHistoryParams = match arguments.map(serde_json::from_value) {
+            Some(Ok(params)) => params,
+            Some(Err(error)) => {
+                return Ok(vec![ToolContent::Text {
+                    text: format!(
+                        "Error: Invalid arguments for conversion_history.\n\nParsing failed with: {}\n\nRequired parameters:\n- action: \"list\", \"repeat\" or \"clear\"\n\nOptional parameters:\n- value: The new value to convert (required for repeat)\n- id: The conversion to repeat\n- limit: Maximum number of entries to list\n- offset: Number of entries to skip",
+                        error
+                    ),
+                }]);
+            }
+            None => {
+                return Ok(vec![ToolContent::Text {
+                    text: "Error: Missing arguments for conversion_history.\n\nTo use this tool, please provide:\n- action: \"list\", \"repeat\" or \"clear\"\n\nExample: {\"action\": \"repeat\", \"value\": 42}".to_string(),
+                }]);
+            }
+        };
+
+        let response = match params.action.to_lowercase().as_str() {
+            "list" => {
+                let (page, pagination) = super::paginate(
+                    self.history.list(),
+                    params.offset.unwrap_or(0),
+                    params.limit.unwrap_or(10),
+                );
+                json!({
+                    "conversions": page,
+                    "pagination": pagination
+                })
+            }
+            "repeat" => {
+                let Some(value) = params.value else {
+                    return Ok(vec![ToolContent::Text {
+                        text: "Error: repeat needs a value to convert (e.g., {\"action\": \"repeat\", \"value\": 42}).".to_string(),
+                    }]);
+                };
+                let Some(mut item) = self.history.find(params.id) else {
+                    return Ok(vec![ToolContent::Text {
+                        text: match params.id {
+                            Some(id) => format!(
+                                "Error: No conversion with id {} in the history. Use the list action to see the available ids.",
+                                id
+                            ),
+                            None => {
+                                "Error: The history is empty; there is no conversion to repeat."
+                                    .to_string()
+                            }
+                        },
+                    }]);
+                };
+
+                item.value = value;
+                match self.unit_conversion.convert_single(&item).await {
+                    Ok(result) => {
+                        self.history.record(&item, &result);
+                        result
+                    }
+                    Err(error) => {
+                        return Ok(vec![ToolContent::Text {
+                            text: format!("Error: {}", error),
+                        }]);
+                    }
+                }
+            }
+            "clear" => json!({ "cleared": self.history.clear() }),
+            other => {
+                return Ok(vec![ToolContent::Text {
+                    text: format!(
+                        "Error: Unsupported action: {} (expected list, repeat or clear)",
+                        other
+                    ),
+                }]);
+            }
+        };
+
+        Ok(vec![ToolContent::Text {
+            text: response.to_string(),
+        }])
+    }
+
+    fn to_tool(&self) -> Tool {
+        Tool {
+            name: "conversion_history".to_string(),
+            description: Some("Review the unit conversions performed in this session. List recent conversions, repeat one with a new value (e.g., the last conversion again for 42 instead of 10), or clear the history.".to_string()),
+            input_schema: schema_for!(ConversionHistoryParams).to_value(),
+        }
+    }
+}
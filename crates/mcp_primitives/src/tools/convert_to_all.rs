@@ -0,0 +1,223 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use context_server::{Tool, ToolContent, ToolExecutor};
+use schemars::{JsonSchema, schema_for};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{
+    tools::{AnnotatedTool, ToolAnnotations},
+    units::{self, ConversionContext, Locale, UnitSystem},
+};
+
+#[derive(Deserialize, JsonSchema, Serialize)]
+struct ConvertToAllParams {
+    #[schemars(description = "The value to convert")]
+    value: f64,
+    #[schemars(description = "The unit to convert from")]
+    from_unit: String,
+    #[schemars(
+        description = "Only convert to these units of the same category (e.g., [\"feet\", \"miles\"]); defaults to every unit in the category"
+    )]
+    units: Option<Vec<String>>,
+    #[schemars(description = "Maximum number of conversions to return (default 25, at most 200)")]
+    limit: Option<usize>,
+    #[schemars(
+        description = "Number of conversions to skip, taken from next_offset of a previous page"
+    )]
+    offset: Option<usize>,
+    #[schemars(
+        description = "Display density in dots per inch used for pixel conversions (default 96)"
+    )]
+    dpi: Option<f64>,
+    #[schemars(description = "Font size in pixels that one em represents (default 16)")]
+    base_font_size: Option<f64>,
+    #[schemars(
+        description = "Reference power in watts that 0 dB and a power ratio of 1 stand for, used when converting relative levels to dBm or dBW (default 1)"
+    )]
+    reference: Option<f64>,
+    #[schemars(
+        description = "Language of the unit names and of the number formatting in converted (en, es, fr, it, de or pt). Defaults to English names and plain numbers"
+    )]
+    locale: Option<String>,
+}
+
+pub struct ConvertToAll {
+    default_system: Option<UnitSystem>,
+}
+
+impl Default for ConvertToAll {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConvertToAll {
+    pub const fn new() -> Self {
+        Self {
+            default_system: None,
+        }
+    }
+
+    /// Reads system-dependent terms such as "gallons" or "cups" the way `system` does.
+    pub const fn with_default_system(mut self, system: UnitSystem) -> Self {
+        self.default_system = Some(system);
+        self
+    }
+}
+
+impl AnnotatedTool for ConvertToAll {
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only("Convert to All Units")
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for ConvertToAll {
+    async fn execute(&self, arguments: Option<Value>) -> Result<Vec<ToolContent>> {
+        let params: ConvertToAllParams = match arguments.map(serde_json::from_value) {
+            Some(Ok(params)) => params,
+            Some(Err(error)) => {
+                return Ok(vec![ToolContent::Text {
+                    text: format!(
+                        "Error: Invalid arguments for convert_to_all.\n\nParsing failed with: {}\n\nRequired parameters:\n- value: A number (e.g., 10.5)\n- from_unit: A string specifying the source unit\n\nOptional parameters:\n- units: An array of target unit names\n- limit: Maximum number of conversions to return\n- offset: Number of conversions to skip",
+                        error
+                    ),
+                }]);
+            }
+            None => {
+                return Ok(vec![ToolContent::Text {
+                    text: "Error: Missing arguments for convert_to_all.\n\nTo use this tool, please provide:\n- value: The numeric value to convert (e.g., 10)\n- from_unit: The source unit (e.g., \"meters\")\n\nExample: {\"value\": 10, \"from_unit\": \"meters\", \"limit\": 5}".to_string(),
+                }]);
+            }
+        };
+
+        let locale = match params
+            .locale
+            .as_deref()
+            .map(str::parse::<Locale>)
+            .transpose()
+        {
+            Ok(locale) => locale,
+            Err(error) => {
+                return Ok(vec![ToolContent::Text {
+                    text: format!("Error: {}", error),
+                }]);
+            }
+        };
+
+        let from_unit = units::localize(&params.from_unit, locale);
+        if let Some(error) = units::disambiguate(from_unit, self.default_system) {
+            return Ok(vec![ToolContent::Text {
+                text: format!("Error: {}", error),
+            }]);
+        }
+        let Some(from) = units::resolve_all(from_unit, self.default_system)
+            .first()
+            .copied()
+        else {
+            return Ok(vec![ToolContent::Text {
+                text: format!(
+                    "Error: Unrecognized source unit \"{}\". Use the list_units tool to see supported units.",
+                    params.from_unit
+                ),
+            }]);
+        };
+
+        let context = match ConversionContext::from_parameters(
+            params.dpi,
+            params.base_font_size,
+            params.reference,
+        ) {
+            Ok(context) => context,
+            Err(error) => {
+                return Ok(vec![ToolContent::Text {
+                    text: format!("Error: {}", error),
+                }]);
+            }
+        };
+
+        let targets: Vec<_> = match &params.units {
+            Some(names) => {
+                let mut targets = Vec::new();
+                for name in names {
+                    match units::resolve_all(units::localize(name, locale), self.default_system)
+                        .into_iter()
+                        .find(|def| def.unit_type == from.unit_type)
+                    {
+                        Some(def) => targets.push(def),
+                        None => {
+                            return Ok(vec![ToolContent::Text {
+                                text: format!(
+                                    "Error: \"{}\" is not a {} unit.\n\nSupported {} units: {}",
+                                    name,
+                                    from.unit_type,
+                                    from.unit_type,
+                                    units::unit_names(from.unit_type).join(", ")
+                                ),
+                            }]);
+                        }
+                    }
+                }
+                targets
+            }
+            None => units::units_of(from.unit_type)
+                .filter(|def| def.name != from.name)
+                .collect(),
+        };
+
+        let base_value = from.conversion.to_base(params.value, &context);
+        let conversions: Vec<Value> = targets
+            .into_iter()
+            .map(|to| {
+                let result = to.conversion.from_base(base_value, &context);
+                if !result.is_finite() {
+                    return json!({
+                        "unit": to.name,
+                        "error": format!(
+                            "{} {} has no equivalent in {}",
+                            params.value, params.from_unit, to.name
+                        )
+                    });
+                }
+                json!({
+                    "unit": to.name,
+                    "converted": format!(
+                        "{} {}",
+                        locale.map_or_else(|| result.to_string(), |locale| locale.format_number(result)),
+                        to.name
+                    ),
+                    "value": result
+                })
+            })
+            .collect();
+
+        let (page, pagination) = super::paginate(
+            conversions,
+            params.offset.unwrap_or(0),
+            params.limit.unwrap_or(25),
+        );
+
+        Ok(vec![ToolContent::Text {
+            text: json!({
+                "original": format!("{} {}", params.value, params.from_unit),
+                "unit_type": from.unit_type.to_string(),
+                "conversions": page,
+                "pagination": pagination
+            })
+            .to_string(),
+        }])
+    }
+
+    fn to_tool(&self) -> Tool {
+        Tool {
+            name: "convert_to_all".to_string(),
+            description: Some("Convert a value into every other unit of its category (e.g., 10 meters into kilometers, miles, feet and so on). Narrow the output with units, and page through long categories with limit and offset; pagination.next_offset is set while more conversions remain. Units the value has no equivalent in, such as decibels for 0 W, carry an error in place of a value.".to_string()),
+            input_schema: {
+                let mut schema = schema_for!(ConvertToAllParams).to_value();
+                super::describe_unit_fields(&mut schema, "/properties", &["from_unit"], &[]);
+                schema
+            },
+        }
+    }
+}
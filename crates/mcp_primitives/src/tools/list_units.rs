@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use context_server::{Tool, ToolContent, ToolExecutor};
+use schemars::{JsonSchema, schema_for};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{
+    plugins::Plugins,
+    tools::{AnnotatedTool, ToolAnnotations},
+    units::{self, UnitType},
+};
+
+#[derive(Default, Deserialize, JsonSchema, Serialize)]
+struct ListUnitsParams {
+    #[schemars(
+        description = "Only list units of this category (e.g., distance, weight, pressure)"
+    )]
+    category: Option<String>,
+    #[schemars(description = "Maximum number of units to return (default 50, at most 200)")]
+    limit: Option<usize>,
+    #[schemars(description = "Number of units to skip, taken from next_offset of a previous page")]
+    offset: Option<usize>,
+}
+
+pub struct ListUnits {
+    plugins: Option<Arc<Plugins>>,
+}
+
+impl Default for ListUnits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ListUnits {
+    pub const fn new() -> Self {
+        Self { plugins: None }
+    }
+
+    /// Lists the units of plugin executables after the built-in ones.
+    pub fn with_plugins(mut self, plugins: Arc<Plugins>) -> Self {
+        self.plugins = Some(plugins);
+        self
+    }
+}
+
+impl AnnotatedTool for ListUnits {
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only("List Units")
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for ListUnits {
+    async fn execute(&self, arguments: Option<Value>) -> Result<Vec<ToolContent>> {
+        let params: ListUnitsParams = match arguments {
+            Some(arguments) => match serde_json::from_value(arguments) {
+                Ok(params) => params,
+                Err(error) => {
+                    return Ok(vec![ToolContent::Text {
+                        text: format!(
+                            "Error: Invalid arguments for list_units.\n\nParsing failed with: {}\n\nOptional parameters:\n- category: A category name such as \"distance\"\n- limit: Maximum number of units to return\n- offset: Number of units to skip",
+                            error
+                        ),
+                    }]);
+                }
+            },
+            None => ListUnitsParams::default(),
+        };
+
+        let plugin_categories = self
+            .plugins
+            .as_ref()
+            .map(|plugins| plugins.categories())
+            .unwrap_or_default();
+        let category = params.category.as_deref().map(str::to_lowercase);
+        if let Some(category) = &category
+            && category.parse::<UnitType>().is_err()
+            && !plugin_categories.contains(&category.as_str())
+        {
+            let categories: Vec<String> = units::categories()
+                .into_iter()
+                .map(|unit_type| unit_type.to_string())
+                .chain(
+                    plugin_categories
+                        .iter()
+                        .map(|category| category.to_string()),
+                )
+                .collect();
+            return Ok(vec![ToolContent::Text {
+                text: format!(
+                    "Error: Unsupported category: {}.\n\nSupported categories: {}",
+                    category,
+                    categories.join(", ")
+                ),
+            }]);
+        }
+
+        let mut entries: Vec<Value> = units::catalog()
+            .filter(|def| {
+                category
+                    .as_ref()
+                    .is_none_or(|category| def.unit_type.to_string() == *category)
+            })
+            .map(|def| {
+                json!({
+                    "name": def.name,
+                    "symbol": def.symbol,
+                    "aliases": def.aliases,
+                    "category": def.unit_type.to_string()
+                })
+            })
+            .collect();
+        if let Some(plugins) = &self.plugins {
+            entries.extend(
+                plugins
+                    .units()
+                    .filter(|(_, unit)| {
+                        category
+                            .as_ref()
+                            .is_none_or(|category| unit.category == *category)
+                    })
+                    .map(|(plugin, unit)| {
+                        json!({
+                            "name": unit.name,
+                            "symbol": unit.symbol,
+                            "aliases": unit.aliases,
+                            "category": unit.category,
+                            "plugin": plugin.name()
+                        })
+                    }),
+            );
+        }
+
+        entries.sort_by_cached_key(|entry| {
+            let category = entry["category"].as_str().unwrap_or_default().to_string();
+            let name = entry["name"].as_str().unwrap_or_default().to_string();
+            (units::category_rank(&category), category, name)
+        });
+
+        let (page, pagination) = super::paginate(
+            entries,
+            params.offset.unwrap_or(0),
+            params.limit.unwrap_or(50),
+        );
+
+        Ok(vec![ToolContent::Text {
+            text: json!({
+                "units": page,
+                "pagination": pagination
+            })
+            .to_string(),
+        }])
+    }
+
+    fn to_tool(&self) -> Tool {
+        Tool {
+            name: "list_units".to_string(),
+            description: Some("List the units supported by unit_conversion with their symbols, accepted aliases and category. Results are paginated; pass pagination.next_offset back as offset to fetch the next page, or filter by category to keep responses small.".to_string()),
+            input_schema: schema_for!(ListUnitsParams).to_value(),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use context_server::{Tool, ToolContent, ToolExecutor};
+use schemars::{JsonSchema, schema_for};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{
+    tools::{AnnotatedTool, ToolAnnotations},
+    units::{self, ConversionContext, UnitType},
+};
+
+#[derive(Deserialize, JsonSchema, Serialize)]
+struct RackCapacityParams {
+    #[schemars(description = "Height of each device in rack units (e.g., 2 for a 2U server)")]
+    device_units: f64,
+    #[schemars(description = "Usable height of the rack in rack units (default 42)")]
+    rack_units: Option<f64>,
+    #[schemars(
+        description = "Rack units already taken by other equipment such as switches, patch panels or PDUs (default 0)"
+    )]
+    reserved_units: Option<f64>,
+}
+
+pub struct RackCapacity;
+
+impl Default for RackCapacity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RackCapacity {
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// `rack_units` expressed in millimeters and inches.
+    fn height(rack_units: f64) -> Result<Value> {
+        let context = ConversionContext::default();
+        // Looked up within Distance, as millimeters and inches are also typography units.
+        let distance_unit = |name: &str| {
+            units::units_of(UnitType::Distance)
+                .find(|def| def.name == name)
+                .ok_or_else(|| anyhow!("{} is not a registered distance unit", name))
+        };
+        let base = distance_unit("rack_units")?
+            .conversion
+            .to_base(rack_units, &context);
+        let in_unit =
+            |name: &str| distance_unit(name).map(|def| def.conversion.from_base(base, &context));
+
+        Ok(json!({
+            "millimeters": in_unit("millimeters")?,
+            "inches": in_unit("inches")?
+        }))
+    }
+}
+
+impl AnnotatedTool for RackCapacity {
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only("Rack Capacity")
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for RackCapacity {
+    async fn execute(&self, arguments: Option<Value>) -> Result<Vec<ToolContent>> {
+        let params: RackCapacityParams = match arguments.map(serde_json::from_value) {
+            Some(Ok(params)) => params,
+            Some(Err(error)) => {
+                return Ok(vec![ToolContent::Text {
+                    text: format!(
+                        "Error: Invalid arguments for rack_capacity.\n\nParsing failed with: {}\n\nRequired parameters:\n- device_units: Height of each device in rack units (e.g., 2)\n\nOptional parameters:\n- rack_units: Usable height of the rack (default 42)\n- reserved_units: Rack units already taken",
+                        error
+                    ),
+                }]);
+            }
+            None => {
+                return Ok(vec![ToolContent::Text {
+                    text: "Error: Missing arguments for rack_capacity.\n\nTo use this tool, please provide:\n- device_units: Height of each device in rack units\n\nExample: {\"device_units\": 2, \"rack_units\": 42}".to_string(),
+                }]);
+            }
+        };
+
+        let rack_units = params.rack_units.unwrap_or(42.0);
+        let reserved_units = params.reserved_units.unwrap_or(0.0);
+        if params.device_units <= 0.0 || rack_units <= 0.0 || reserved_units < 0.0 {
+            return Ok(vec![ToolContent::Text {
+                text: "Error: device_units and rack_units must be greater than zero, and reserved_units cannot be negative.".to_string(),
+            }]);
+        }
+        if reserved_units > rack_units {
+            return Ok(vec![ToolContent::Text {
+                text: format!(
+                    "Error: {}U reserved exceeds the {}U rack.",
+                    reserved_units, rack_units
+                ),
+            }]);
+        }
+
+        let available_units = rack_units - reserved_units;
+        let devices = (available_units / params.device_units).floor();
+        let used_units = devices * params.device_units;
+
+        Ok(vec![ToolContent::Text {
+            text: json!({
+                "devices": devices,
+                "summary": format!(
+                    "{} × {}U devices fit in a {}U rack with {}U reserved, leaving {}U free",
+                    devices,
+                    params.device_units,
+                    rack_units,
+                    reserved_units,
+                    available_units - used_units
+                ),
+                "rack_units": rack_units,
+                "reserved_units": reserved_units,
+                "used_units": used_units,
+                "free_units": available_units - used_units,
+                "rack_height": Self::height(rack_units)?
+            })
+            .to_string(),
+        }])
+    }
+
+    fn to_tool(&self) -> Tool {
+        Tool {
+            name: "rack_capacity".to_string(),
+            description: Some("Work out how many devices of a given height fit in a server rack (e.g., how many 2U servers fit in a 42U rack), accounting for rack units already taken by other equipment. Heights are in rack units (1U = 1.75 in = 44.45 mm); convert rack units to other lengths with unit_conversion.".to_string()),
+            input_schema: schema_for!(RackCapacityParams).to_value(),
+        }
+    }
+}
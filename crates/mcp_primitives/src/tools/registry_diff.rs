@@ -0,0 +1,67 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use context_server::{Tool, ToolContent, ToolExecutor};
+use schemars::{JsonSchema, schema_for};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    tools::{AnnotatedTool, ToolAnnotations},
+    units::manifest::Manifest,
+};
+
+#[derive(Deserialize, JsonSchema, Serialize)]
+struct RegistryDiffParams {}
+
+pub struct RegistryDiff {
+    version: &'static str,
+    previous_run: Option<Manifest>,
+}
+
+impl RegistryDiff {
+    /// Compares the registry of this run, labelled with the server `version`, against
+    /// nothing until [`RegistryDiff::with_previous_run`] supplies a baseline.
+    pub const fn new(version: &'static str) -> Self {
+        Self {
+            version,
+            previous_run: None,
+        }
+    }
+
+    /// Compares against the registry a previous run saved.
+    pub fn with_previous_run(mut self, manifest: Manifest) -> Self {
+        self.previous_run = Some(manifest);
+        self
+    }
+}
+
+impl AnnotatedTool for RegistryDiff {
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only("Registry Diff")
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for RegistryDiff {
+    async fn execute(&self, _arguments: Option<Value>) -> Result<Vec<ToolContent>> {
+        let Some(previous_run) = &self.previous_run else {
+            return Ok(vec![ToolContent::Text {
+                text: "Error: No previous run is recorded. Set UNIT_CONVERSION_REGISTRY_SNAPSHOT to a file path so each run saves its registry for the next one.".to_string(),
+            }]);
+        };
+
+        Ok(vec![ToolContent::Text {
+            text: previous_run
+                .diff(&Manifest::current(self.version))
+                .to_string(),
+        }])
+    }
+
+    fn to_tool(&self) -> Tool {
+        Tool {
+            name: "registry_diff".to_string(),
+            description: Some("List the units added, removed or changed (symbol, category or conversion factor) since the server's previous run, with both server versions. Use it after an upgrade or a change to the configured units to check whether earlier conversion results still hold.".to_string()),
+            input_schema: schema_for!(RegistryDiffParams).to_value(),
+        }
+    }
+}
@@ -0,0 +1,887 @@
+use std::{fmt, sync::Arc};
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use context_server::{Tool, ToolContent, ToolExecutor};
+use schemars::{JsonSchema, schema_for};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{
+    diagnostics,
+    plugins::Plugins,
+    telemetry::Telemetry,
+    tools::{AnnotatedTool, History, ToolAnnotations},
+    units::{
+        self, ConversionContext, Locale, UnitDefinition, UnitSystem, UnitType, compound,
+        plausibility::PlausibilityBounds,
+    },
+};
+
+#[derive(Clone, Deserialize, JsonSchema, Serialize)]
+pub(crate) struct UnitConversionItem {
+    #[schemars(description = "The value to convert")]
+    pub(crate) value: f64,
+    #[schemars(description = "The unit to convert from")]
+    pub(crate) from_unit: String,
+    #[schemars(
+        description = "The target unit to convert to (or \"auto\" to pick the most readable unit in the source's category)"
+    )]
+    pub(crate) to_unit: String,
+    #[schemars(
+        description = "Display density in dots per inch used for pixel conversions (default 96)"
+    )]
+    dpi: Option<f64>,
+    #[schemars(description = "Font size in pixels that one em represents (default 16)")]
+    base_font_size: Option<f64>,
+    #[schemars(
+        description = "Reference power in watts that 0 dB and a power ratio of 1 stand for, used when converting relative levels to dBm or dBW (default 1)"
+    )]
+    reference: Option<f64>,
+    #[schemars(
+        description = "Language of the unit names and of the number formatting in converted (en, es, fr, it, de or pt; e.g., \"metros\" with es). Defaults to English names and plain numbers"
+    )]
+    locale: Option<String>,
+    #[schemars(
+        description = "How to format converted: \"plain\" (default), \"weather\", which quotes pressure the way forecasts do (hPa and mbar without decimals, inHg to two decimals), or \"mixed\", which splits hands, feet and stones into whole units and a remainder (e.g., 15.2 hh for 15 hands 2 inches, 5 ft 11 in, 11 st 6 lb). Only results are formatted; value is read as a plain number, so 15 hands 2 inches is 15.5 hands"
+    )]
+    format: Option<String>,
+    #[schemars(
+        description = "Report unit_type as the domain the units measure, such as \"depth\" for fathoms or \"altitude\" for flight levels, instead of the general category (default false)"
+    )]
+    domain_types: Option<bool>,
+    #[schemars(
+        description = "How units are written in original, converted and reverse: \"given\" repeats the units as requested (default), \"name\" uses full words (kilometers), \"symbol\" uses symbols (km), and \"localized\" uses words in the requested locale (kilómetros with es)"
+    )]
+    unit_style: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema, Serialize)]
+struct UnitConversionParams {
+    #[schemars(description = "Array of unit conversions to perform")]
+    conversions: Vec<UnitConversionItem>,
+    #[schemars(
+        description = "When the batch or its response exceeds the server's size limits, return the leading conversions that fit instead of an error (default false)"
+    )]
+    allow_partial: Option<bool>,
+}
+
+/// Presentation of `converted` requested through an item's `format`.
+#[derive(Clone, Copy)]
+enum OutputFormat {
+    Plain,
+    Weather,
+    Mixed,
+}
+
+/// How unit names are written in formatted strings, chosen through an item's `unit_style`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum UnitStyle {
+    Given,
+    Name,
+    Symbol,
+    Localized,
+}
+
+/// Caps that keep a single tool call from flooding the stdio transport.
+#[derive(Clone, Copy, Debug)]
+pub struct BatchLimits {
+    /// Maximum number of entries accepted in `conversions`.
+    pub max_batch_items: usize,
+    /// Maximum size, in bytes, of the serialized tool response.
+    pub max_response_bytes: usize,
+}
+
+impl Default for BatchLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatchLimits {
+    pub const fn new() -> Self {
+        Self {
+            max_batch_items: 1_000,
+            max_response_bytes: 1024 * 1024,
+        }
+    }
+}
+
+/// Bytes kept free for the response envelope around results and errors.
+const RESPONSE_ENVELOPE_BYTES: usize = 512;
+
+/// A conversion between two recognised units of one category whose result is not a
+/// finite number, such as 0 mpg in L/100 km.
+#[derive(Debug)]
+pub(crate) struct NoEquivalent {
+    message: String,
+    /// The reading with from_unit and to_unit swapped, when that one converts.
+    swap_hint: Option<Value>,
+}
+
+impl fmt::Display for NoEquivalent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for NoEquivalent {}
+
+pub struct UnitConversion {
+    default_system: Option<UnitSystem>,
+    limits: BatchLimits,
+    telemetry: Option<Arc<Telemetry>>,
+    plugins: Option<Arc<Plugins>>,
+    history: Option<Arc<History>>,
+    plausibility: Option<Arc<PlausibilityBounds>>,
+}
+
+impl Default for UnitConversion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UnitConversion {
+    pub const fn new() -> Self {
+        Self {
+            default_system: None,
+            limits: BatchLimits::new(),
+            telemetry: None,
+            plugins: None,
+            history: None,
+            plausibility: None,
+        }
+    }
+
+    /// Reads system-dependent terms such as "gallons" or "cups" the way `system` does.
+    pub const fn with_default_system(mut self, system: UnitSystem) -> Self {
+        self.default_system = Some(system);
+        self
+    }
+
+    pub const fn with_limits(mut self, limits: BatchLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Tallies the category pair of every requested conversion into `telemetry`.
+    pub fn with_telemetry(mut self, telemetry: Arc<Telemetry>) -> Self {
+        self.telemetry = Some(telemetry);
+        self
+    }
+
+    /// Falls back to units provided by plugin executables when no built-in unit matches.
+    pub fn with_plugins(mut self, plugins: Arc<Plugins>) -> Self {
+        self.plugins = Some(plugins);
+        self
+    }
+
+    /// Records every successful conversion into `history`.
+    pub fn with_history(mut self, history: Arc<History>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Warns about converted values outside the real-world `bounds` of their category.
+    pub fn with_plausibility(mut self, bounds: Arc<PlausibilityBounds>) -> Self {
+        self.plausibility = Some(bounds);
+        self
+    }
+
+    fn limit_error(code: &str, message: String, limit: usize, received: usize) -> Vec<ToolContent> {
+        vec![ToolContent::Text {
+            text: json!({
+                "error": {
+                    "code": code,
+                    "message": message,
+                    "limit": limit,
+                    "received": received
+                },
+                "total_conversions": 0,
+                "success": false
+            })
+            .to_string(),
+        }]
+    }
+
+    fn description(&self) -> String {
+        let mut categories = units::categories()
+            .iter()
+            .map(|unit_type| {
+                format!(
+                    "{} ({})",
+                    unit_type.label().to_lowercase(),
+                    units::unit_names(*unit_type).join(", ")
+                )
+            })
+            .collect::<Vec<_>>();
+        if let Some(plugins) = &self.plugins {
+            categories.extend(plugins.categories().into_iter().map(|category| {
+                let mut names: Vec<&str> = plugins
+                    .units()
+                    .filter(|(_, unit)| unit.category == category)
+                    .map(|(_, unit)| unit.name.as_str())
+                    .collect();
+                names.sort_unstable();
+                format!("{} ({})", category, names.join(", "))
+            }));
+        }
+        let categories = categories.join(", ");
+        let system_note = self
+            .default_system
+            .map(|system| (system, units::system_dependent_units(system)))
+            .filter(|(_, names)| !names.is_empty())
+            .map(|(system, names)| {
+                format!(
+                    " This server reads {} as {} units; prefix them with us_ (e.g., us_{}) for the US customary ones.",
+                    names.join(", ").replace('_', " "),
+                    system,
+                    names[0]
+                )
+            })
+            .unwrap_or_default();
+
+        format!(
+            "Convert between different units including {}.{} Pixels and ems honour the optional dpi and base_font_size parameters. Compound expressions such as g/cm^3, kg*m/s^2 or m^3/h are converted by dimensional analysis, with s, min, h, d, Hz, N, J and W available alongside the units above; rates may also be worded, as in \"dollars per gallon\". Set to_unit to \"auto\" to have the most readable unit chosen (e.g., 1536000000 bytes becomes 1.43 GB). Results include reverse, one target unit expressed in the source unit, for \"how many X per Y\" questions. A warning is added when a value falls outside the real-world range this server was configured with, which often means the source value or units were mixed up; swap_hint then suggests the reading with from_unit and to_unit reversed, and hints does the same for conversions with no finite result, naming each by its position. Accepts an array of conversions to process multiple unit conversions at once.",
+            categories, system_note
+        )
+    }
+
+    fn supported_units_by_category() -> String {
+        units::categories()
+            .iter()
+            .map(|unit_type| {
+                format!(
+                    "{}: {}",
+                    unit_type.label(),
+                    units::unit_names(*unit_type).join(", ")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn context(item: &UnitConversionItem) -> Result<ConversionContext> {
+        ConversionContext::from_parameters(item.dpi, item.base_font_size, item.reference)
+    }
+
+    fn locale(item: &UnitConversionItem) -> Result<Option<Locale>> {
+        item.locale.as_deref().map(str::parse).transpose()
+    }
+
+    fn output_format(item: &UnitConversionItem) -> Result<OutputFormat> {
+        match item.format.as_deref().map(str::to_lowercase).as_deref() {
+            None | Some("plain") => Ok(OutputFormat::Plain),
+            Some("weather") => Ok(OutputFormat::Weather),
+            Some("mixed") => Ok(OutputFormat::Mixed),
+            Some(other) => Err(anyhow!(
+                "Unsupported format: {} (expected plain, weather or mixed)",
+                other
+            )),
+        }
+    }
+
+    /// The `unit_type` reported for a conversion between `units`, which is the first
+    /// domain among them when the item asks for domain types.
+    fn unit_type(
+        item: &UnitConversionItem,
+        unit_type: UnitType,
+        units: &[&UnitDefinition],
+    ) -> String {
+        units
+            .iter()
+            .filter(|_| item.domain_types.unwrap_or(false))
+            .find_map(|unit| units::domain(unit))
+            .map_or_else(|| unit_type.to_string(), str::to_string)
+    }
+
+    fn unit_style(item: &UnitConversionItem) -> Result<Option<UnitStyle>> {
+        match item.unit_style.as_deref().map(str::to_lowercase).as_deref() {
+            None => Ok(None),
+            Some("given") => Ok(Some(UnitStyle::Given)),
+            Some("name") => Ok(Some(UnitStyle::Name)),
+            Some("symbol") => Ok(Some(UnitStyle::Symbol)),
+            Some("localized") => Ok(Some(UnitStyle::Localized)),
+            Some(other) => Err(anyhow!(
+                "Unsupported unit_style: {} (expected given, name, symbol or localized)",
+                other
+            )),
+        }
+    }
+
+    /// `unit` written in `style` for a quantity of `value`, or `given` when no style applies.
+    /// Localized words fall back to the English name where the locale has none.
+    fn unit_label(
+        style: Option<UnitStyle>,
+        unit: &UnitDefinition,
+        value: f64,
+        given: &str,
+        locale: Option<Locale>,
+    ) -> String {
+        match style {
+            None | Some(UnitStyle::Given) => given.to_string(),
+            Some(UnitStyle::Symbol) => unit.symbol.to_string(),
+            Some(UnitStyle::Name) => unit.name.replace('_', " "),
+            Some(UnitStyle::Localized) => locale
+                .and_then(|locale| units::localized_name(unit, locale, value))
+                .map_or_else(|| unit.name.replace('_', " "), str::to_string),
+        }
+    }
+
+    fn format_number(value: f64, locale: Option<Locale>) -> String {
+        locale.map_or_else(|| value.to_string(), |locale| locale.format_number(value))
+    }
+
+    pub(crate) async fn convert_single(&self, item: &UnitConversionItem) -> Result<Value> {
+        let locale = Self::locale(item)?;
+        let format = Self::output_format(item)?;
+        let style = Self::unit_style(item)?;
+        if item.to_unit.eq_ignore_ascii_case("auto") {
+            return self.humanize_single(item, locale, style);
+        }
+
+        let from_unit = units::localize(&item.from_unit, locale);
+        let to_unit = units::localize(&item.to_unit, locale);
+        let (from, to) = match units::resolve_pair(from_unit, to_unit, self.default_system) {
+            Ok(pair) => pair,
+            Err(error) => {
+                if let Some(plugins) = &self.plugins
+                    && plugins.find(from_unit).is_some()
+                    && plugins.find(to_unit).is_some()
+                {
+                    return self
+                        .plugin_single(plugins, item, from_unit, to_unit, locale)
+                        .await;
+                }
+                let pair = [from_unit, to_unit];
+                let is_compound = pair
+                    .into_iter()
+                    .any(|unit| units::resolve_all(unit, self.default_system).is_empty())
+                    && pair
+                        .into_iter()
+                        .all(|unit| units::disambiguate(unit, self.default_system).is_none());
+                if !is_compound {
+                    return Err(error);
+                }
+                return self.compound_single(item, from_unit, to_unit, locale);
+            }
+        };
+        let context = Self::context(item)?;
+        let unit_type = from.unit_type;
+        let base_value = from.conversion.to_base(item.value, &context);
+        let result = to.conversion.from_base(base_value, &context);
+        if !result.is_finite() {
+            return Err(NoEquivalent {
+                message: format!(
+                    "{} {} has no equivalent in {}",
+                    item.value, item.from_unit, item.to_unit
+                ),
+                swap_hint: self.swapped_units_hint(item, from, to, &context),
+            }
+            .into());
+        }
+
+        let converted = match format {
+            OutputFormat::Weather => units::format_weather(result, to).map(|number| {
+                format!(
+                    "{} {}",
+                    locale.map_or(number.clone(), |locale| locale.localize_number(&number)),
+                    to.symbol
+                )
+            }),
+            OutputFormat::Mixed => units::format_mixed(result, to),
+            OutputFormat::Plain => None,
+        }
+        .unwrap_or_else(|| {
+            format!(
+                "{} {}",
+                Self::format_number(result, locale),
+                Self::unit_label(style, to, result, &item.to_unit, locale)
+            )
+        });
+
+        let mut response = json!({
+            "original": format!(
+                "{} {}",
+                item.value,
+                Self::unit_label(style, from, item.value, &item.from_unit, locale)
+            ),
+            "converted": converted,
+            "value": result,
+            "unit_type": Self::unit_type(item, unit_type, &[to, from])
+        });
+        if let Some(caveat) = [from, to].into_iter().find_map(units::approximation) {
+            response["approximate"] = json!(true);
+            response["note"] = json!(caveat);
+        }
+        if self.add_plausibility_warning(&mut response, unit_type, base_value)
+            && let Some(hint) = self.swap_hint(item, from, to, &context)
+        {
+            response["swap_hint"] = hint;
+        }
+        let reverse = from
+            .conversion
+            .from_base(to.conversion.to_base(1.0, &context), &context);
+        Self::add_reverse(
+            &mut response,
+            reverse,
+            &Self::unit_label(style, to, 1.0, &item.to_unit, locale),
+            &Self::unit_label(style, from, reverse, &item.from_unit, locale),
+            locale,
+        );
+        Ok(response)
+    }
+
+    /// Adds `reverse`, one target unit expressed in source units (`1 km = 0.62 mi` for a
+    /// miles to kilometers conversion), so "how many X per Y" questions need no second
+    /// call. Omitted when the target unit has no equivalent in the source.
+    fn add_reverse(
+        response: &mut Value,
+        value: f64,
+        to_unit: &str,
+        from_unit: &str,
+        locale: Option<Locale>,
+    ) {
+        if value.is_finite() {
+            response["reverse"] = json!({
+                "value": value,
+                "text": format!("1 {} = {} {}", to_unit, Self::format_number(value, locale), from_unit)
+            });
+        }
+    }
+
+    /// Adds `warning` when `base_value` falls outside the configured bounds of `unit_type`,
+    /// returning whether it did.
+    fn add_plausibility_warning(
+        &self,
+        response: &mut Value,
+        unit_type: UnitType,
+        base_value: f64,
+    ) -> bool {
+        let warning = self
+            .plausibility
+            .as_ref()
+            .and_then(|bounds| bounds.check(unit_type, base_value));
+        if let Some(warning) = &warning {
+            response["warning"] = json!(warning);
+        }
+        warning.is_some()
+    }
+
+    /// Reads an implausible `item` the other way round: when its value is plausible as a
+    /// quantity of `to`, the value was probably already in the target unit or the units were
+    /// swapped, which callers do surprisingly often.
+    fn swap_hint(
+        &self,
+        item: &UnitConversionItem,
+        from: &UnitDefinition,
+        to: &UnitDefinition,
+        context: &ConversionContext,
+    ) -> Option<Value> {
+        let swapped = self.swapped_value(item, from, to, context)?;
+
+        Some(json!({
+            "from_unit": item.to_unit,
+            "to_unit": item.from_unit,
+            "value": swapped,
+            "message": format!(
+                "{} {} is plausible, so the value may already be in {}, or from_unit and to_unit may be swapped ({} {} = {} {})",
+                item.value, item.to_unit, item.to_unit, item.value, item.to_unit, units::format_readable(swapped), item.from_unit
+            )
+        }))
+    }
+
+    /// A hint for an `item` with no finite result, when reading it with from_unit and
+    /// to_unit swapped gives a finite value within the configured bounds.
+    fn swapped_units_hint(
+        &self,
+        item: &UnitConversionItem,
+        from: &UnitDefinition,
+        to: &UnitDefinition,
+        context: &ConversionContext,
+    ) -> Option<Value> {
+        let swapped = self.swapped_value(item, from, to, context)?;
+
+        Some(json!({
+            "from_unit": item.to_unit,
+            "to_unit": item.from_unit,
+            "value": swapped,
+            "message": format!(
+                "from_unit and to_unit may be swapped: {} {} = {} {}",
+                item.value, item.to_unit, units::format_readable(swapped), item.from_unit
+            )
+        }))
+    }
+
+    /// `item`'s value read as a quantity of `to` and converted to `from`, unless the result
+    /// is not finite or falls outside the configured bounds.
+    fn swapped_value(
+        &self,
+        item: &UnitConversionItem,
+        from: &UnitDefinition,
+        to: &UnitDefinition,
+        context: &ConversionContext,
+    ) -> Option<f64> {
+        let base_value = to.conversion.to_base(item.value, context);
+        let plausible = self
+            .plausibility
+            .as_ref()
+            .is_none_or(|bounds| bounds.check(to.unit_type, base_value).is_none());
+        let swapped = from.conversion.from_base(base_value, context);
+        (plausible && swapped.is_finite()).then_some(swapped)
+    }
+
+    /// Category of `unit` for telemetry, without echoing anything the caller typed.
+    fn category_of(&self, unit: &str) -> String {
+        if let Some(def) = units::resolve_all(unit, self.default_system).first() {
+            return def.unit_type.to_string();
+        }
+        if let Some((_, plugin_unit)) = self.plugins.as_ref().and_then(|plugins| plugins.find(unit))
+        {
+            return plugin_unit.category.clone();
+        }
+
+        match compound::parse(unit, self.default_system) {
+            Ok(compound) => compound
+                .dimension
+                .quantity()
+                .unwrap_or("compound")
+                .to_string(),
+            Err(_) => "unsupported".to_string(),
+        }
+    }
+
+    async fn plugin_single(
+        &self,
+        plugins: &Plugins,
+        item: &UnitConversionItem,
+        from_unit: &str,
+        to_unit: &str,
+        locale: Option<Locale>,
+    ) -> Result<Value> {
+        let result = plugins.convert(item.value, from_unit, to_unit).await?;
+        let (plugin, unit) = plugins
+            .find(from_unit)
+            .ok_or_else(|| anyhow!("Unsupported unit: {}", from_unit))?;
+
+        Ok(json!({
+            "original": format!("{} {}", item.value, item.from_unit),
+            "converted": format!("{} {}", Self::format_number(result, locale), item.to_unit),
+            "value": result,
+            "unit_type": unit.category,
+            "plugin": plugin.name()
+        }))
+    }
+
+    fn compound_single(
+        &self,
+        item: &UnitConversionItem,
+        from_unit: &str,
+        to_unit: &str,
+        locale: Option<Locale>,
+    ) -> Result<Value> {
+        let (result, dimension) =
+            compound::convert(item.value, from_unit, to_unit, self.default_system)?;
+
+        let mut response = json!({
+            "original": format!("{} {}", item.value, item.from_unit),
+            "converted": format!("{} {}", Self::format_number(result, locale), item.to_unit),
+            "value": result,
+            "unit_type": dimension
+                .quantity()
+                .map_or_else(|| dimension.to_string(), str::to_string),
+            "dimension": dimension.to_string()
+        });
+        if let Ok((reverse, _)) = compound::convert(1.0, to_unit, from_unit, self.default_system) {
+            Self::add_reverse(
+                &mut response,
+                reverse,
+                &item.to_unit,
+                &item.from_unit,
+                locale,
+            );
+        }
+        Ok(response)
+    }
+
+    fn humanize_single(
+        &self,
+        item: &UnitConversionItem,
+        locale: Option<Locale>,
+        style: Option<UnitStyle>,
+    ) -> Result<Value> {
+        let from_unit = units::localize(&item.from_unit, locale);
+        if let Some(error) = units::disambiguate(from_unit, self.default_system) {
+            return Err(anyhow!("{}", error));
+        }
+        let from = *units::resolve_all(from_unit, self.default_system)
+            .first()
+            .ok_or_else(|| anyhow!("Unsupported unit: {}", item.from_unit))?;
+        let context = Self::context(item)?;
+        let (unit, result) = units::humanize(from, item.value, self.default_system, &context);
+        let readable = units::format_readable(result);
+
+        let mut response = json!({
+            "original": format!(
+                "{} {}",
+                item.value,
+                Self::unit_label(style, from, item.value, &item.from_unit, locale)
+            ),
+            "converted": format!(
+                "{} {}",
+                locale.map_or(readable.clone(), |locale| locale.localize_number(&readable)),
+                Self::unit_label(style, unit, result, unit.symbol, locale)
+            ),
+            "value": result,
+            "unit": unit.name,
+            "unit_type": Self::unit_type(item, unit.unit_type, &[from])
+        });
+        self.add_plausibility_warning(
+            &mut response,
+            from.unit_type,
+            from.conversion.to_base(item.value, &context),
+        );
+        let reverse = from
+            .conversion
+            .from_base(unit.conversion.to_base(1.0, &context), &context);
+        Self::add_reverse(
+            &mut response,
+            reverse,
+            &Self::unit_label(style, unit, 1.0, unit.symbol, locale),
+            &Self::unit_label(style, from, reverse, &item.from_unit, locale),
+            locale,
+        );
+        Ok(response)
+    }
+}
+
+impl AnnotatedTool for UnitConversion {
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only("Unit Conversion")
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for UnitConversion {
+    async fn execute(&self, arguments: Option<Value>) -> Result<Vec<ToolContent>> {
+        let arguments = match arguments {
+            Some(args) => args,
+            None => {
+                return Ok(vec![ToolContent::Text {
+                    text: "Error: Missing arguments for unit conversion.\n\nTo use this tool, please provide:\n- conversions: An array of conversion objects, where each object contains:\n  - value: The numeric value to convert (e.g., 10)\n  - from_unit: The source unit (e.g., \"meters\", \"pounds\", \"celsius\")\n  - to_unit: The target unit (e.g., \"feet\", \"kilograms\", \"fahrenheit\")\n\nExample: {\"conversions\": [{\"value\": 10, \"from_unit\": \"meters\", \"to_unit\": \"feet\"}, {\"value\": 32, \"from_unit\": \"fahrenheit\", \"to_unit\": \"celsius\"}]}".to_string(),
+                }]);
+            }
+        };
+
+        let params: UnitConversionParams = match serde_json::from_value(arguments) {
+            Ok(params) => params,
+            Err(error) => {
+                return Ok(vec![ToolContent::Text {
+                    text: format!(
+                        "Error: Invalid arguments for unit conversion.\n\nParsing failed with: {}\n\nRequired parameters:\n- conversions: An array of conversion objects\n- Each conversion object must contain:\n  - value: A number (e.g., 10.5)\n  - from_unit: A string specifying the source unit\n  - to_unit: A string specifying the target unit\n\nPlease ensure your JSON is properly formatted and includes all required fields.",
+                        error
+                    ),
+                }]);
+            }
+        };
+
+        if params.conversions.is_empty() {
+            return Ok(vec![ToolContent::Text {
+                text: "Error: No conversions provided. Please include at least one conversion in the conversions array.".to_string(),
+            }]);
+        }
+
+        let received = params.conversions.len();
+        let allow_partial = params.allow_partial.unwrap_or(false);
+        let max_items = self.limits.max_batch_items;
+        if received > max_items && !allow_partial {
+            return Ok(Self::limit_error(
+                "batch_too_large",
+                format!(
+                    "Error: The batch contains {} conversions but this server accepts at most {}. Split the request into smaller batches, or set allow_partial to true to convert the first {}.",
+                    received, max_items, max_items
+                ),
+                max_items,
+                received,
+            ));
+        }
+
+        if let Some(telemetry) = &self.telemetry {
+            let pairs = params.conversions.iter().take(max_items).map(|item| {
+                let locale = Self::locale(item).ok().flatten();
+                let from = self.category_of(units::localize(&item.from_unit, locale));
+                let to = if item.to_unit.eq_ignore_ascii_case("auto") {
+                    from.clone()
+                } else {
+                    self.category_of(units::localize(&item.to_unit, locale))
+                };
+                (from, to)
+            });
+            if let Err(error) = telemetry.record(pairs).await {
+                diagnostics::report(format_args!("Error recording telemetry: {:#}", error));
+            }
+        }
+
+        let mut results = Vec::new();
+        let mut errors = Vec::new();
+        let mut hints = Vec::new();
+        let mut truncated = (received > max_items).then(|| {
+            json!({
+                "code": "batch_too_large",
+                "limit": max_items,
+                "processed": max_items,
+                "received": received
+            })
+        });
+
+        let budget = self
+            .limits
+            .max_response_bytes
+            .saturating_sub(RESPONSE_ENVELOPE_BYTES);
+        let mut used_bytes = 0;
+
+        for (index, item) in params.conversions.iter().take(max_items).enumerate() {
+            let mut hint = None;
+            let outcome = match self.convert_single(item).await {
+                Ok(result) => Ok(result),
+                Err(e) => {
+                    hint = e
+                        .downcast_ref::<NoEquivalent>()
+                        .and_then(|failure| failure.swap_hint.clone())
+                        .map(|mut hint| {
+                            hint["conversion"] = json!(index + 1);
+                            hint
+                        });
+                    let error_msg = if e.to_string().contains("Unsupported unit:") {
+                        if e.to_string().contains(&item.from_unit) {
+                            format!(
+                                "Conversion #{}: Unrecognized source unit \"{}\".\n\nSupported units by category:\n\n{}\n\nNote: Units are case-insensitive. Try using the full unit name or common abbreviations.",
+                                index + 1,
+                                item.from_unit,
+                                Self::supported_units_by_category()
+                            )
+                        } else if let Some(from) = units::resolve_all(
+                            units::localize(&item.from_unit, Self::locale(item).ok().flatten()),
+                            self.default_system,
+                        )
+                        .first()
+                        {
+                            let unit_type = from.unit_type;
+                            format!(
+                                "Conversion #{}: Cannot convert from {} ({}) to \"{}\".\n\nThe target unit \"{}\" is either:\n1. Not supported for {} conversions\n2. From a different unit category\n3. Misspelled\n\nSupported {} units: {}\n\nNote: You can only convert between units of the same type (e.g., distance to distance, weight to weight).",
+                                index + 1,
+                                item.from_unit,
+                                unit_type,
+                                item.to_unit,
+                                item.to_unit,
+                                unit_type,
+                                unit_type,
+                                units::unit_names(unit_type).join(", ")
+                            )
+                        } else {
+                            format!("Conversion #{}: {}", index + 1, e)
+                        }
+                    } else {
+                        format!("Conversion #{}: {}", index + 1, e)
+                    };
+                    Err(error_msg)
+                }
+            };
+
+            used_bytes += match &outcome {
+                Ok(result) => result.to_string().len(),
+                Err(error_msg) => Value::from(error_msg.as_str()).to_string().len(),
+            } + hint.as_ref().map_or(0, |hint| hint.to_string().len() + 1)
+                + 1;
+            if used_bytes > budget {
+                if !allow_partial {
+                    return Ok(Self::limit_error(
+                        "response_too_large",
+                        format!(
+                            "Error: The response would exceed this server's limit of {} bytes after {} of {} conversions. Split the request into smaller batches, or set allow_partial to true to receive the conversions that fit.",
+                            self.limits.max_response_bytes, index, received
+                        ),
+                        self.limits.max_response_bytes,
+                        received,
+                    ));
+                }
+                truncated = Some(json!({
+                    "code": "response_too_large",
+                    "limit": self.limits.max_response_bytes,
+                    "processed": index,
+                    "received": received
+                }));
+                break;
+            }
+
+            match outcome {
+                Ok(result) => {
+                    if let Some(history) = &self.history {
+                        history.record(item, &result);
+                    }
+                    results.push(result);
+                }
+                Err(error_msg) => errors.push(error_msg),
+            }
+            hints.extend(hint);
+        }
+
+        let mut response = if errors.is_empty() {
+            json!({
+                "results": results,
+                "total_conversions": results.len(),
+                "success": true
+            })
+        } else if results.is_empty() {
+            json!({
+                "errors": errors,
+                "total_conversions": 0,
+                "success": false
+            })
+        } else {
+            json!({
+                "results": results,
+                "errors": errors,
+                "total_conversions": results.len(),
+                "total_errors": errors.len(),
+                "success": true,
+                "partial": true
+            })
+        };
+
+        if let Some(truncated) = truncated {
+            response["truncated"] = truncated;
+            response["partial"] = json!(true);
+        }
+        if !hints.is_empty() {
+            response["hints"] = json!(hints);
+        }
+
+        Ok(vec![ToolContent::Text {
+            text: response.to_string(),
+        }])
+    }
+
+    fn to_tool(&self) -> Tool {
+        Tool {
+            name: "unit_conversion".to_string(),
+            description: Some(self.description()),
+            input_schema: {
+                let mut schema = schema_for!(UnitConversionParams).to_value();
+                let plugin_categories = self
+                    .plugins
+                    .as_ref()
+                    .map(|plugins| plugins.categories())
+                    .unwrap_or_default();
+                super::describe_unit_fields(
+                    &mut schema,
+                    "/$defs/UnitConversionItem/properties",
+                    &["from_unit", "to_unit"],
+                    &plugin_categories,
+                );
+                schema
+            },
+        }
+    }
+}
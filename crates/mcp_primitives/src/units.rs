@@ -0,0 +1,2471 @@
+use std::{
+    fmt,
+    str::FromStr,
+    sync::{Arc, LazyLock, PoisonError, RwLock},
+};
+
+use anyhow::{Result, anyhow};
+
+pub mod compound;
+pub mod config;
+pub mod manifest;
+pub mod plausibility;
+pub mod script;
+pub mod sets;
+
+use script::Script;
+use sets::UnitSet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnitType {
+    Distance,
+    Volume,
+    Weight,
+    Temperature,
+    Digital,
+    Pressure,
+    Speed,
+    DataRate,
+    Typography,
+    Level,
+    Slope,
+    FuelEconomy,
+    Viscosity,
+    Time,
+    Energy,
+}
+
+impl UnitType {
+    pub const ALL: [UnitType; 15] = [
+        UnitType::Distance,
+        UnitType::Volume,
+        UnitType::Weight,
+        UnitType::Temperature,
+        UnitType::Digital,
+        UnitType::Pressure,
+        UnitType::Speed,
+        UnitType::DataRate,
+        UnitType::Typography,
+        UnitType::Level,
+        UnitType::Slope,
+        UnitType::FuelEconomy,
+        UnitType::Viscosity,
+        UnitType::Time,
+        UnitType::Energy,
+    ];
+
+    /// Capitalized category name for headings and listings.
+    pub const fn label(self) -> &'static str {
+        match self {
+            UnitType::Distance => "Distance",
+            UnitType::Volume => "Volume",
+            UnitType::Weight => "Weight",
+            UnitType::Temperature => "Temperature",
+            UnitType::Digital => "Digital",
+            UnitType::Pressure => "Pressure",
+            UnitType::Speed => "Speed",
+            UnitType::DataRate => "Data rate",
+            UnitType::Typography => "Typography",
+            UnitType::Level => "Level",
+            UnitType::Slope => "Slope",
+            UnitType::FuelEconomy => "Fuel economy",
+            UnitType::Viscosity => "Viscosity",
+            UnitType::Time => "Time",
+            UnitType::Energy => "Energy",
+        }
+    }
+
+    /// The unit every conversion in this category goes through.
+    pub const fn base_unit(self) -> &'static str {
+        match self {
+            UnitType::Distance => "meters",
+            UnitType::Volume => "liters",
+            UnitType::Weight => "kilograms",
+            UnitType::Temperature => "celsius",
+            UnitType::Digital => "bytes",
+            UnitType::Pressure => "pascal",
+            UnitType::Speed => "meters_per_second",
+            UnitType::DataRate => "bits_per_second",
+            UnitType::Typography => "points",
+            UnitType::Level => "power_ratio",
+            UnitType::Slope => "slope_ratio",
+            UnitType::FuelEconomy => "kilometers_per_liter",
+            UnitType::Viscosity => "centistokes",
+            UnitType::Time => "seconds",
+            UnitType::Energy => "joules",
+        }
+    }
+
+    pub const fn description(self) -> &'static str {
+        match self {
+            UnitType::Distance => {
+                "Length and distance, from inches on a ruler to nautical miles on a chart. Metric units are defined from the meter; the international yard and pound agreement of 1959 fixed the imperial units to exact metric values."
+            }
+            UnitType::Volume => {
+                "Liquid volume as used in cooking, fuel and beverages. The US customary units here (gallons, quarts, pints, cups, fluid ounces) differ from their imperial namesakes."
+            }
+            UnitType::Weight => {
+                "Mass, colloquially weight, as measured on kitchen, body and shipping scales. Avoirdupois pounds and ounces are the everyday imperial units."
+            }
+            UnitType::Temperature => {
+                "Temperature scales. Unlike other categories these do not share a common zero, so conversions apply an offset as well as a scale factor."
+            }
+            UnitType::Digital => {
+                "Digital storage. Multiples use binary (1024-based) steps, matching how operating systems usually report file and memory sizes."
+            }
+            UnitType::Pressure => {
+                "Pressure as found in weather reports, tyre gauges, diving and industrial equipment. The SI unit, the pascal, is one newton per square meter."
+            }
+            UnitType::Speed => {
+                "Speed of vehicles, vessels and wind. The Beaufort scale is an empirical wind force scale and converts to representative speeds rather than exact values."
+            }
+            UnitType::DataRate => {
+                "Data transfer rates for network links, downloads and streaming. Unlike storage sizes, rate multiples are decimal (1 Mbps is 1,000,000 bits per second), as used by ISPs and networking equipment."
+            }
+            UnitType::Typography => {
+                "Type and screen measurements for print and web design. Physical units follow the desktop publishing point of 1/72 inch; pixels depend on the display density (dpi, 96 by default as in CSS) and ems on the base font size (16 px by default)."
+            }
+            UnitType::Level => {
+                "Power and signal levels on logarithmic decibel scales alongside the linear ratios they express, as used in acoustics, audio and radio engineering. Relative levels compare against the optional reference power (1 W by default); dBm, dBW, watts and milliwatts are absolute."
+            }
+            UnitType::Slope => {
+                "Gradients of roads, railways, ramps and terrain, as the rise over the horizontal run. Railways quote them per mille or as 1 in N, roads in percent; 1 in 100 is 1% or 10‰."
+            }
+            UnitType::FuelEconomy => {
+                "Fuel efficiency of cars, aircraft and boats, either as distance per volume of fuel (higher is better) or fuel used over a distance (lower is better). US and imperial miles per gallon differ because their gallons do; the marine units count nautical miles."
+            }
+            UnitType::Viscosity => {
+                "Kinematic viscosity of oils, lubricants and fuels. Centistokes (mm²/s) are the modern measure; Saybolt Universal Seconds from older specifications convert through an empirical correlation, so those results are approximate."
+            }
+            UnitType::Time => {
+                "Durations from seconds to days. Calendar units such as months and years vary in length and are left out."
+            }
+            UnitType::Energy => {
+                "Energy and work, from food labels in calories to electricity bills in kilowatt-hours. The SI unit, the joule, is one newton acting over one meter."
+            }
+        }
+    }
+}
+
+impl fmt::Display for UnitType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitType::Distance => write!(f, "distance"),
+            UnitType::Volume => write!(f, "volume"),
+            UnitType::Weight => write!(f, "weight"),
+            UnitType::Temperature => write!(f, "temperature"),
+            UnitType::Digital => write!(f, "digital"),
+            UnitType::Pressure => write!(f, "pressure"),
+            UnitType::Speed => write!(f, "speed"),
+            UnitType::DataRate => write!(f, "data_rate"),
+            UnitType::Typography => write!(f, "typography"),
+            UnitType::Level => write!(f, "level"),
+            UnitType::Slope => write!(f, "slope"),
+            UnitType::FuelEconomy => write!(f, "fuel_economy"),
+            UnitType::Viscosity => write!(f, "viscosity"),
+            UnitType::Time => write!(f, "time"),
+            UnitType::Energy => write!(f, "energy"),
+        }
+    }
+}
+
+impl FromStr for UnitType {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.to_lowercase();
+        UnitType::ALL
+            .into_iter()
+            .find(|unit_type| unit_type.to_string() == value)
+            .ok_or_else(|| anyhow!("Unsupported category: {}", value))
+    }
+}
+
+/// Regional convention used to resolve terms such as "gallons" that name different
+/// units in different countries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+    Us,
+}
+
+impl fmt::Display for UnitSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitSystem::Metric => write!(f, "metric"),
+            UnitSystem::Imperial => write!(f, "imperial"),
+            UnitSystem::Us => write!(f, "us"),
+        }
+    }
+}
+
+impl FromStr for UnitSystem {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "metric" => Ok(UnitSystem::Metric),
+            "imperial" => Ok(UnitSystem::Imperial),
+            "us" => Ok(UnitSystem::Us),
+            other => Err(anyhow!(
+                "Unsupported unit system: {} (expected metric, imperial or us)",
+                other
+            )),
+        }
+    }
+}
+
+/// Language used to recognise localized unit names and to format numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Es,
+    Fr,
+    It,
+    De,
+    Pt,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 6] = [
+        Locale::En,
+        Locale::Es,
+        Locale::Fr,
+        Locale::It,
+        Locale::De,
+        Locale::Pt,
+    ];
+
+    pub const fn decimal_separator(self) -> char {
+        match self {
+            Locale::En => '.',
+            Locale::Es | Locale::Fr | Locale::It | Locale::De | Locale::Pt => ',',
+        }
+    }
+
+    pub const fn thousands_separator(self) -> char {
+        match self {
+            Locale::En => ',',
+            Locale::Es | Locale::It | Locale::De | Locale::Pt => '.',
+            Locale::Fr => '\u{202f}',
+        }
+    }
+
+    pub fn format_number(self, value: f64) -> String {
+        self.localize_number(&value.to_string())
+    }
+
+    /// Rewrites a plain decimal such as `-1234.5` with this locale's separators.
+    pub fn localize_number(self, number: &str) -> String {
+        let (sign, digits) = match number.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", number),
+        };
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+        if !integer.bytes().all(|byte| byte.is_ascii_digit()) {
+            return number.to_string();
+        }
+
+        let mut localized = sign.to_string();
+        for (index, digit) in integer.chars().enumerate() {
+            if index > 0 && (integer.len() - index) % 3 == 0 {
+                localized.push(self.thousands_separator());
+            }
+            localized.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            localized.push(self.decimal_separator());
+            localized.push_str(fraction);
+        }
+        localized
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Locale::En => write!(f, "en"),
+            Locale::Es => write!(f, "es"),
+            Locale::Fr => write!(f, "fr"),
+            Locale::It => write!(f, "it"),
+            Locale::De => write!(f, "de"),
+            Locale::Pt => write!(f, "pt"),
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = anyhow::Error;
+
+    /// Accepts a language code, ignoring any region such as the "BR" in "pt-BR".
+    fn from_str(value: &str) -> Result<Self> {
+        let language = value
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.to_string() == language)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unsupported locale: {} (expected one of {})",
+                    value,
+                    Locale::ALL.map(|locale| locale.to_string()).join(", ")
+                )
+            })
+    }
+}
+
+/// Per-request parameters for units whose size is not fixed.
+#[derive(Clone, Copy, Debug)]
+pub struct ConversionContext {
+    /// Display density used to size pixels.
+    pub dpi: f64,
+    /// Font size, in pixels, that one em corresponds to.
+    pub base_font_size: f64,
+    /// Power, in watts, that 0 dB and a ratio of one correspond to.
+    pub reference: f64,
+}
+
+impl Default for ConversionContext {
+    fn default() -> Self {
+        Self {
+            dpi: 96.0,
+            base_font_size: 16.0,
+            reference: 1.0,
+        }
+    }
+}
+
+impl ConversionContext {
+    /// Builds a context from optional tool parameters, rejecting non-positive sizes.
+    pub fn from_parameters(
+        dpi: Option<f64>,
+        base_font_size: Option<f64>,
+        reference: Option<f64>,
+    ) -> Result<Self> {
+        let mut context = Self::default();
+        if let Some(dpi) = dpi {
+            if dpi <= 0.0 {
+                return Err(anyhow!("Invalid dpi: {} (must be greater than zero)", dpi));
+            }
+            context.dpi = dpi;
+        }
+        if let Some(base_font_size) = base_font_size {
+            if base_font_size <= 0.0 {
+                return Err(anyhow!(
+                    "Invalid base_font_size: {} (must be greater than zero)",
+                    base_font_size
+                ));
+            }
+            context.base_font_size = base_font_size;
+        }
+        if let Some(reference) = reference {
+            if reference <= 0.0 {
+                return Err(anyhow!(
+                    "Invalid reference: {} (must be greater than zero)",
+                    reference
+                ));
+            }
+            context.reference = reference;
+        }
+        Ok(context)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Conversion {
+    /// `base = value * factor`
+    Linear(f64),
+    /// `base = value * factor(context)`
+    Contextual(fn(&ConversionContext) -> f64),
+    /// `base = (value - offset) * numerator / denominator`
+    Affine {
+        offset: f64,
+        numerator: f64,
+        denominator: f64,
+    },
+    /// `base = scale * 10^(value / multiplier)`, for decibel-style levels. `scale` is
+    /// `reference_watts / context.reference` for absolute levels such as dBm and one for
+    /// relative levels.
+    Logarithmic {
+        multiplier: f64,
+        reference_watts: Option<f64>,
+    },
+    /// Conversions that cannot be expressed as a scale and an offset.
+    Custom {
+        to_base: fn(f64) -> f64,
+        from_base: fn(f64) -> f64,
+    },
+    /// Custom units from the units file whose conversions are sandboxed scripts over `x`.
+    Scripted {
+        to_base: &'static Script,
+        from_base: &'static Script,
+    },
+}
+
+impl Conversion {
+    pub fn to_base(&self, value: f64, context: &ConversionContext) -> f64 {
+        match *self {
+            Conversion::Linear(factor) => value * factor,
+            Conversion::Contextual(factor) => value * factor(context),
+            Conversion::Affine {
+                offset,
+                numerator,
+                denominator,
+            } => (value - offset) * numerator / denominator,
+            Conversion::Logarithmic {
+                multiplier,
+                reference_watts,
+            } => Self::level_scale(reference_watts, context) * 10f64.powf(value / multiplier),
+            Conversion::Custom { to_base, .. } => to_base(value),
+            Conversion::Scripted { to_base, .. } => Self::run(to_base, value),
+        }
+    }
+
+    pub fn from_base(&self, value: f64, context: &ConversionContext) -> f64 {
+        match *self {
+            Conversion::Linear(factor) => value / factor,
+            Conversion::Contextual(factor) => value / factor(context),
+            Conversion::Affine {
+                offset,
+                numerator,
+                denominator,
+            } => value * denominator / numerator + offset,
+            Conversion::Logarithmic {
+                multiplier,
+                reference_watts,
+            } => multiplier * (value / Self::level_scale(reference_watts, context)).log10(),
+            Conversion::Custom { from_base, .. } => from_base(value),
+            Conversion::Scripted { from_base, .. } => Self::run(from_base, value),
+        }
+    }
+
+    /// Script failures yield NaN, which callers report as a conversion without a result.
+    fn run(script: &Script, value: f64) -> f64 {
+        script.eval(value).unwrap_or_else(|error| {
+            crate::diagnostics::report(format_args!("{:#}", error));
+            f64::NAN
+        })
+    }
+
+    fn level_scale(reference_watts: Option<f64>, context: &ConversionContext) -> f64 {
+        reference_watts.map_or(1.0, |watts| watts / context.reference)
+    }
+}
+
+pub struct UnitDefinition {
+    pub name: &'static str,
+    pub symbol: &'static str,
+    /// Lowercase alternative spellings accepted alongside `name`.
+    pub aliases: &'static [&'static str],
+    pub unit_type: UnitType,
+    pub conversion: Conversion,
+    /// Origin and typical use, surfaced in the documentation resources.
+    pub description: &'static str,
+}
+
+impl UnitDefinition {
+    fn matches(&self, unit: &str) -> bool {
+        self.name == unit || self.aliases.contains(&unit)
+    }
+
+    /// Human-readable summary of how this unit relates to its category's base unit.
+    pub fn conversion_summary(&self) -> String {
+        let base = self.unit_type.base_unit();
+        match self.conversion {
+            Conversion::Linear(factor) => format!("1 {} = {} {}", self.name, factor, base),
+            Conversion::Affine {
+                offset,
+                numerator,
+                denominator,
+            } => format!(
+                "{} = ({} - {}) × {} / {}",
+                base, self.name, offset, numerator, denominator
+            ),
+            Conversion::Contextual(_) => {
+                "depends on request parameters, see description".to_string()
+            }
+            Conversion::Logarithmic {
+                multiplier,
+                reference_watts: None,
+            } => format!("{} = 10^({} / {})", base, self.name, multiplier),
+            Conversion::Logarithmic {
+                multiplier,
+                reference_watts: Some(watts),
+            } => format!(
+                "{} = {} W / reference × 10^({} / {})",
+                base, watts, self.name, multiplier
+            ),
+            Conversion::Custom { .. } => "non-linear, see description".to_string(),
+            Conversion::Scripted { to_base, .. } => {
+                format!("{} = {} with x in {}", base, to_base.source(), self.name)
+            }
+        }
+    }
+}
+
+type Snapshot = Arc<Vec<&'static UnitDefinition>>;
+
+/// The optional unit sets and custom units registered on top of the built-in table.
+struct Layers {
+    sets: Vec<UnitSet>,
+    custom: &'static [UnitDefinition],
+}
+
+impl Layers {
+    /// The default sets and no custom units, as at startup.
+    fn initial() -> Self {
+        Self {
+            sets: UnitSet::DEFAULT.to_vec(),
+            custom: &[],
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Arc::new(
+            UNITS
+                .iter()
+                .chain(self.sets.iter().flat_map(|set| set.units()))
+                .chain(self.custom)
+                .collect(),
+        )
+    }
+
+    /// Swaps in a snapshot of these layers. Callers hold the [`LAYERS`] write lock, so
+    /// concurrent updates publish in the order they were made.
+    fn publish(&self) {
+        *REGISTRY.write().unwrap_or_else(PoisonError::into_inner) = self.snapshot();
+        compound::clear_cache();
+    }
+}
+
+static LAYERS: LazyLock<RwLock<Layers>> = LazyLock::new(|| RwLock::new(Layers::initial()));
+
+/// Built-in units, then enabled unit sets, then custom ones, replaced wholesale on every
+/// update.
+///
+/// Lookups only hold the lock long enough to clone the current snapshot, and updates
+/// build the next one before swapping it in, so a reload never stalls conversions in
+/// flight; those simply finish against the snapshot they started with.
+///
+/// The initial snapshot is built without consulting [`LAYERS`], whose write lock is held
+/// by updates that may be the first to touch the registry.
+static REGISTRY: LazyLock<RwLock<Snapshot>> =
+    LazyLock::new(|| RwLock::new(Layers::initial().snapshot()));
+
+fn registry() -> Snapshot {
+    REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Replaces the custom units (e.g. after reloading the units file), keeping built-ins
+/// and the enabled unit sets.
+///
+/// Definitions are leaked so earlier snapshots stay valid for lookups still using them;
+/// updates are rare administrative events, so the retained memory stays small.
+pub fn replace_custom(units: Vec<UnitDefinition>) {
+    let mut layers = LAYERS.write().unwrap_or_else(PoisonError::into_inner);
+    layers.custom = Box::leak(units.into_boxed_slice());
+    layers.publish();
+}
+
+/// Enables exactly `sets`, replacing [`UnitSet::DEFAULT`] or an earlier selection.
+pub fn enable_sets(sets: Vec<UnitSet>) {
+    let mut layers = LAYERS.write().unwrap_or_else(PoisonError::into_inner);
+    layers.sets = sets;
+    layers.publish();
+}
+
+/// Looks up a unit by its exact symbol, then by name or alias case-insensitively.
+///
+/// Symbols are matched first so case-sensitive spellings such as `MB/s` (megabytes)
+/// and `Mbps` (megabits) resolve to different units.
+pub fn find(unit: &str) -> Option<&'static UnitDefinition> {
+    find_all(unit).into_iter().next()
+}
+
+/// Case-sensitive spellings besides each unit's `symbol`, for rates whose lowercase forms
+/// would read bits as bytes or the other way round (`Mb/s` against `MBps`).
+static EXACT_SPELLINGS: &[(&str, &str)] = &[
+    ("b/s", "bits_per_second"),
+    ("kb/s", "kilobits_per_second"),
+    ("Mb/s", "megabits_per_second"),
+    ("Gb/s", "gigabits_per_second"),
+    ("Bps", "bytes_per_second"),
+    ("MBps", "megabytes_per_second"),
+];
+
+/// Every unit a name can refer to, in lookup order. Units such as millimeters are
+/// registered in more than one category. Names may be written with spaces for
+/// underscores, as in `miles per hour`, and in the singular, as in `gallon`.
+pub fn find_all(unit: &str) -> Vec<&'static UnitDefinition> {
+    let unit_lower = unit.to_lowercase();
+    let registry = registry();
+    if let Some((_, name)) = EXACT_SPELLINGS
+        .iter()
+        .find(|(spelling, _)| *spelling == unit)
+    {
+        return registry
+            .iter()
+            .copied()
+            .filter(|def| def.name == *name)
+            .collect();
+    }
+
+    let mut matches: Vec<&'static UnitDefinition> = registry
+        .iter()
+        .copied()
+        .filter(|def| def.symbol == unit)
+        .collect();
+    for def in registry
+        .iter()
+        .copied()
+        .filter(|def| def.matches(&unit_lower))
+    {
+        if !matches.iter().any(|found| std::ptr::eq(*found, def)) {
+            matches.push(def);
+        }
+    }
+
+    if matches.is_empty() && unit.trim().contains(char::is_whitespace) {
+        return find_all(&unit.split_whitespace().collect::<Vec<_>>().join("_"));
+    }
+    if matches.is_empty() && unit_lower.chars().all(|c| c.is_alphabetic() || c == '_') {
+        matches = registry
+            .iter()
+            .copied()
+            .filter(|def| {
+                def.name == format!("{}s", unit_lower) || def.name == format!("{}es", unit_lower)
+            })
+            .collect();
+    }
+    matches
+}
+
+/// Decimal places meteorologists quote pressure in, by unit. Used by the weather format.
+static WEATHER_DECIMALS: &[(&str, usize)] = &[
+    ("hectopascals", 0),
+    ("millibars", 0),
+    ("kilopascal", 1),
+    ("inches_of_mercury", 2),
+    ("mmhg", 0),
+];
+
+/// Domains of units that only measure one kind of quantity, as `(unit name, domain)`.
+/// Reported in place of the category when a client asks for domain-aware unit types.
+static UNIT_DOMAINS: &[(&str, &str)] = &[
+    ("fathoms", "depth"),
+    ("flight_levels", "altitude"),
+    ("meters_msl", "altitude"),
+];
+
+/// The domain `unit` measures, such as `"depth"` for fathoms, if it is tied to one.
+pub fn domain(unit: &UnitDefinition) -> Option<&'static str> {
+    UNIT_DOMAINS
+        .iter()
+        .find(|(name, _)| *name == unit.name)
+        .map(|(_, domain)| *domain)
+}
+
+/// Units whose conversions follow an empirical correlation or band rather than an exact
+/// definition, as `(unit name, caveat)`. Results involving them are flagged approximate.
+static APPROXIMATIONS: &[(&str, &str)] = &[
+    (
+        "beaufort",
+        "Beaufort forces stand for a band of wind speeds; a force converts to a representative speed within its band.",
+    ),
+    (
+        "saybolt_universal_seconds",
+        "Saybolt seconds are converted with the empirical ASTM D2161 correlation for 100 °F (37.8 °C) rather than an exact definition.",
+    ),
+];
+
+/// Why conversions involving `unit` are approximate, if they are.
+pub fn approximation(unit: &UnitDefinition) -> Option<&'static str> {
+    APPROXIMATIONS
+        .iter()
+        .find(|(name, _)| *name == unit.name)
+        .map(|(_, caveat)| *caveat)
+}
+
+/// Formats `value` in `unit` the way a weather report would, e.g. `1013 hPa` or
+/// `29.92 inHg`. Units without a weather convention are `None`.
+pub fn format_weather(value: f64, unit: &UnitDefinition) -> Option<String> {
+    WEATHER_DECIMALS
+        .iter()
+        .find(|(name, _)| *name == unit.name)
+        .map(|(_, decimals)| format!("{:.*}", decimals, value))
+}
+
+/// How a mixed quantity is written once split into whole units and a remainder.
+enum MixedNotation {
+    /// The remainder after a dot, then a single symbol, as in `15.2 hh`.
+    Dotted(&'static str),
+    /// Each part followed by its own symbol, as in `5 ft 11 in`.
+    Spaced(&'static str, &'static str),
+}
+
+/// Units customarily written as whole units plus a remainder in a smaller unit, as
+/// `(unit name, smaller units per unit, notation)`. Used by the mixed format.
+static MIXED_FORMATS: &[(&str, f64, MixedNotation)] = &[
+    ("hands", 4.0, MixedNotation::Dotted("hh")),
+    ("feet", 12.0, MixedNotation::Spaced("ft", "in")),
+    ("stones", 14.0, MixedNotation::Spaced("st", "lb")),
+];
+
+/// Formats `value` in `unit` as whole units and a remainder rounded to the nearest
+/// smaller unit, e.g. `15.2 hh` or `5 ft 11 in`. Units without a mixed convention are
+/// `None`.
+pub fn format_mixed(value: f64, unit: &UnitDefinition) -> Option<String> {
+    let (_, per_unit, notation) = MIXED_FORMATS
+        .iter()
+        .find(|(name, _, _)| *name == unit.name)?;
+    let total = (value.abs() * per_unit).round();
+    let whole = (total / per_unit).floor();
+    let remainder = total - whole * per_unit;
+    let sign = if value < 0.0 && total > 0.0 { "-" } else { "" };
+
+    Some(match notation {
+        MixedNotation::Dotted(symbol) => format!("{}{}.{} {}", sign, whole, remainder, symbol),
+        MixedNotation::Spaced(major, minor) => {
+            format!("{}{} {} {} {}", sign, whole, major, remainder, minor)
+        }
+    })
+}
+
+/// Unit names in other languages, as `(locale, unit, aliases)`. Aliases are lowercase and
+/// only recognised when the request names their locale.
+static LOCALIZED_ALIASES: &[(Locale, &str, &[&str])] = &[
+    (Locale::Es, "meters", &["metro", "metros"]),
+    (
+        Locale::Es,
+        "kilometers",
+        &["kilómetro", "kilómetros", "kilometro", "kilometros"],
+    ),
+    (
+        Locale::Es,
+        "centimeters",
+        &["centímetro", "centímetros", "centimetro", "centimetros"],
+    ),
+    (
+        Locale::Es,
+        "millimeters",
+        &["milímetro", "milímetros", "milimetro", "milimetros"],
+    ),
+    (Locale::Es, "miles", &["milla", "millas"]),
+    (Locale::Es, "feet", &["pie", "pies"]),
+    (Locale::Es, "inches", &["pulgada", "pulgadas"]),
+    (Locale::Es, "yards", &["yarda", "yardas"]),
+    (Locale::Es, "liters", &["litro", "litros"]),
+    (Locale::Es, "milliliters", &["mililitro", "mililitros"]),
+    (Locale::Es, "gallons", &["galón", "galones", "galon"]),
+    (Locale::Es, "kilograms", &["kilogramo", "kilogramos"]),
+    (Locale::Es, "grams", &["gramo", "gramos"]),
+    (Locale::Es, "pounds", &["libra", "libras"]),
+    (Locale::Es, "ounces", &["onza", "onzas"]),
+    (
+        Locale::Es,
+        "celsius",
+        &["grado celsius", "grados celsius", "centígrados"],
+    ),
+    (
+        Locale::Fr,
+        "meters",
+        &["mètre", "mètres", "metre", "metres"],
+    ),
+    (
+        Locale::Fr,
+        "kilometers",
+        &["kilomètre", "kilomètres", "kilometre", "kilometres"],
+    ),
+    (
+        Locale::Fr,
+        "centimeters",
+        &["centimètre", "centimètres", "centimetre", "centimetres"],
+    ),
+    (
+        Locale::Fr,
+        "millimeters",
+        &["millimètre", "millimètres", "millimetre", "millimetres"],
+    ),
+    (Locale::Fr, "miles", &["mille", "milles"]),
+    (Locale::Fr, "feet", &["pied", "pieds"]),
+    (Locale::Fr, "inches", &["pouce", "pouces"]),
+    (Locale::Fr, "liters", &["litre", "litres"]),
+    (Locale::Fr, "milliliters", &["millilitre", "millilitres"]),
+    (Locale::Fr, "kilograms", &["kilogramme", "kilogrammes"]),
+    (Locale::Fr, "grams", &["gramme", "grammes"]),
+    (Locale::Fr, "pounds", &["livre", "livres"]),
+    (Locale::Fr, "ounces", &["once", "onces"]),
+    (Locale::Fr, "bytes", &["octet", "octets"]),
+    (Locale::Fr, "kilobytes", &["kilooctet", "kilooctets", "ko"]),
+    (Locale::Fr, "megabytes", &["mégaoctet", "mégaoctets", "mo"]),
+    (Locale::Fr, "gigabytes", &["gigaoctet", "gigaoctets", "go"]),
+    (Locale::Fr, "terabytes", &["téraoctet", "téraoctets", "to"]),
+    (Locale::It, "meters", &["metro", "metri"]),
+    (Locale::It, "kilometers", &["chilometro", "chilometri"]),
+    (Locale::It, "centimeters", &["centimetro", "centimetri"]),
+    (Locale::It, "millimeters", &["millimetro", "millimetri"]),
+    (Locale::It, "miles", &["miglio", "miglia"]),
+    (Locale::It, "feet", &["piede", "piedi"]),
+    (Locale::It, "inches", &["pollice", "pollici"]),
+    (Locale::It, "liters", &["litro", "litri"]),
+    (Locale::It, "milliliters", &["millilitro", "millilitri"]),
+    (
+        Locale::It,
+        "kilograms",
+        &["chilogrammo", "chilogrammi", "chilo", "chili"],
+    ),
+    (Locale::It, "grams", &["grammo", "grammi"]),
+    (Locale::It, "pounds", &["libbra", "libbre"]),
+    (Locale::It, "ounces", &["oncia", "once"]),
+    (Locale::De, "meters", &["meter"]),
+    (Locale::De, "kilometers", &["kilometer"]),
+    (Locale::De, "centimeters", &["zentimeter"]),
+    (Locale::De, "millimeters", &["millimeter"]),
+    (Locale::De, "miles", &["meile", "meilen"]),
+    (Locale::De, "feet", &["fuß", "fuss"]),
+    (Locale::De, "inches", &["zoll"]),
+    (Locale::De, "liters", &["liter"]),
+    (Locale::De, "milliliters", &["milliliter"]),
+    (Locale::De, "kilograms", &["kilogramm"]),
+    (Locale::De, "grams", &["gramm"]),
+    (Locale::De, "pounds", &["pfund"]),
+    (Locale::De, "ounces", &["unze", "unzen"]),
+    (Locale::Pt, "meters", &["metro", "metros"]),
+    (
+        Locale::Pt,
+        "kilometers",
+        &["quilômetro", "quilômetros", "quilómetro", "quilómetros"],
+    ),
+    (Locale::Pt, "centimeters", &["centímetro", "centímetros"]),
+    (Locale::Pt, "millimeters", &["milímetro", "milímetros"]),
+    (Locale::Pt, "miles", &["milha", "milhas"]),
+    (Locale::Pt, "feet", &["pé", "pés"]),
+    (Locale::Pt, "inches", &["polegada", "polegadas"]),
+    (Locale::Pt, "liters", &["litro", "litros"]),
+    (Locale::Pt, "milliliters", &["mililitro", "mililitros"]),
+    (Locale::Pt, "gallons", &["galão", "galões"]),
+    (
+        Locale::Pt,
+        "kilograms",
+        &["quilograma", "quilogramas", "quilo", "quilos"],
+    ),
+    (Locale::Pt, "grams", &["grama", "gramas"]),
+    (Locale::Pt, "pounds", &["libra", "libras"]),
+    (Locale::Pt, "ounces", &["onça", "onças"]),
+];
+
+/// How units are written in other languages, as `(locale, unit, singular, plural)`.
+/// Separate from [`LOCALIZED_ALIASES`], which lists every spelling accepted as input.
+static LOCALIZED_NAMES: &[(Locale, &str, &str, &str)] = &[
+    (Locale::Es, "meters", "metro", "metros"),
+    (Locale::Es, "kilometers", "kilómetro", "kilómetros"),
+    (Locale::Es, "centimeters", "centímetro", "centímetros"),
+    (Locale::Es, "millimeters", "milímetro", "milímetros"),
+    (Locale::Es, "miles", "milla", "millas"),
+    (Locale::Es, "feet", "pie", "pies"),
+    (Locale::Es, "inches", "pulgada", "pulgadas"),
+    (Locale::Es, "yards", "yarda", "yardas"),
+    (Locale::Es, "liters", "litro", "litros"),
+    (Locale::Es, "milliliters", "mililitro", "mililitros"),
+    (Locale::Es, "gallons", "galón", "galones"),
+    (Locale::Es, "kilograms", "kilogramo", "kilogramos"),
+    (Locale::Es, "grams", "gramo", "gramos"),
+    (Locale::Es, "pounds", "libra", "libras"),
+    (Locale::Es, "ounces", "onza", "onzas"),
+    (Locale::Es, "celsius", "grado Celsius", "grados Celsius"),
+    (Locale::Fr, "meters", "mètre", "mètres"),
+    (Locale::Fr, "kilometers", "kilomètre", "kilomètres"),
+    (Locale::Fr, "centimeters", "centimètre", "centimètres"),
+    (Locale::Fr, "millimeters", "millimètre", "millimètres"),
+    (Locale::Fr, "miles", "mille", "milles"),
+    (Locale::Fr, "feet", "pied", "pieds"),
+    (Locale::Fr, "inches", "pouce", "pouces"),
+    (Locale::Fr, "liters", "litre", "litres"),
+    (Locale::Fr, "milliliters", "millilitre", "millilitres"),
+    (Locale::Fr, "kilograms", "kilogramme", "kilogrammes"),
+    (Locale::Fr, "grams", "gramme", "grammes"),
+    (Locale::Fr, "pounds", "livre", "livres"),
+    (Locale::Fr, "ounces", "once", "onces"),
+    (Locale::Fr, "bytes", "octet", "octets"),
+    (Locale::Fr, "kilobytes", "kilooctet", "kilooctets"),
+    (Locale::Fr, "megabytes", "mégaoctet", "mégaoctets"),
+    (Locale::Fr, "gigabytes", "gigaoctet", "gigaoctets"),
+    (Locale::Fr, "terabytes", "téraoctet", "téraoctets"),
+    (Locale::It, "meters", "metro", "metri"),
+    (Locale::It, "kilometers", "chilometro", "chilometri"),
+    (Locale::It, "centimeters", "centimetro", "centimetri"),
+    (Locale::It, "millimeters", "millimetro", "millimetri"),
+    (Locale::It, "miles", "miglio", "miglia"),
+    (Locale::It, "feet", "piede", "piedi"),
+    (Locale::It, "inches", "pollice", "pollici"),
+    (Locale::It, "liters", "litro", "litri"),
+    (Locale::It, "milliliters", "millilitro", "millilitri"),
+    (Locale::It, "kilograms", "chilogrammo", "chilogrammi"),
+    (Locale::It, "grams", "grammo", "grammi"),
+    (Locale::It, "pounds", "libbra", "libbre"),
+    (Locale::It, "ounces", "oncia", "once"),
+    (Locale::De, "meters", "Meter", "Meter"),
+    (Locale::De, "kilometers", "Kilometer", "Kilometer"),
+    (Locale::De, "centimeters", "Zentimeter", "Zentimeter"),
+    (Locale::De, "millimeters", "Millimeter", "Millimeter"),
+    (Locale::De, "miles", "Meile", "Meilen"),
+    (Locale::De, "feet", "Fuß", "Fuß"),
+    (Locale::De, "inches", "Zoll", "Zoll"),
+    (Locale::De, "liters", "Liter", "Liter"),
+    (Locale::De, "milliliters", "Milliliter", "Milliliter"),
+    (Locale::De, "kilograms", "Kilogramm", "Kilogramm"),
+    (Locale::De, "grams", "Gramm", "Gramm"),
+    (Locale::De, "pounds", "Pfund", "Pfund"),
+    (Locale::De, "ounces", "Unze", "Unzen"),
+    (Locale::Pt, "meters", "metro", "metros"),
+    (Locale::Pt, "kilometers", "quilômetro", "quilômetros"),
+    (Locale::Pt, "centimeters", "centímetro", "centímetros"),
+    (Locale::Pt, "millimeters", "milímetro", "milímetros"),
+    (Locale::Pt, "miles", "milha", "milhas"),
+    (Locale::Pt, "feet", "pé", "pés"),
+    (Locale::Pt, "inches", "polegada", "polegadas"),
+    (Locale::Pt, "liters", "litro", "litros"),
+    (Locale::Pt, "milliliters", "mililitro", "mililitros"),
+    (Locale::Pt, "gallons", "galão", "galões"),
+    (Locale::Pt, "kilograms", "quilograma", "quilogramas"),
+    (Locale::Pt, "grams", "grama", "gramas"),
+    (Locale::Pt, "pounds", "libra", "libras"),
+    (Locale::Pt, "ounces", "onça", "onças"),
+];
+
+/// The word for `unit` in `locale`'s language, singular when `value` is one and plural
+/// otherwise, if the localized names cover it.
+pub fn localized_name(unit: &UnitDefinition, locale: Locale, value: f64) -> Option<&'static str> {
+    LOCALIZED_NAMES
+        .iter()
+        .find(|(name_locale, name, _, _)| *name_locale == locale && *name == unit.name)
+        .map(|(_, _, singular, plural)| {
+            if value.abs() == 1.0 {
+                *singular
+            } else {
+                *plural
+            }
+        })
+}
+
+/// Maps a unit name in `locale`'s language to the registry name, leaving other names as is.
+pub fn localize(unit: &str, locale: Option<Locale>) -> &str {
+    let Some(locale) = locale else {
+        return unit;
+    };
+    let unit_lower = unit.trim().to_lowercase();
+    LOCALIZED_ALIASES
+        .iter()
+        .find(|(alias_locale, _, aliases)| {
+            *alias_locale == locale && aliases.contains(&unit_lower.as_str())
+        })
+        .map_or(unit, |(_, name, _)| name)
+}
+
+/// Units and terms whose meaning depends on the unit system, as `(term, system, unit
+/// name)`. A term naming a registry unit covers every spelling of that unit (its symbol,
+/// aliases, singular and worded forms) except the explicitly US ones, such as us_mpg;
+/// other terms, such as cwt, are matched as written. Systems without an entry keep the
+/// regular registry meaning, which follows US customary usage.
+static SYSTEM_VARIANTS: &[(&str, UnitSystem, &str)] = &[
+    ("gallons", UnitSystem::Imperial, "imperial_gallons"),
+    ("quarts", UnitSystem::Imperial, "imperial_quarts"),
+    ("pints", UnitSystem::Imperial, "imperial_pints"),
+    (
+        "fluid_ounces",
+        UnitSystem::Imperial,
+        "imperial_fluid_ounces",
+    ),
+    ("cups", UnitSystem::Metric, "metric_cups"),
+    (
+        "miles_per_gallon",
+        UnitSystem::Imperial,
+        "imperial_miles_per_gallon",
+    ),
+    ("cwt", UnitSystem::Us, "us_hundredweight"),
+    ("hundredweight", UnitSystem::Us, "us_hundredweight"),
+    ("cwt", UnitSystem::Imperial, "uk_hundredweight"),
+    ("hundredweight", UnitSystem::Imperial, "uk_hundredweight"),
+];
+
+/// Registry units that `system` reads as a different unit, such as gallons as imperial
+/// gallons. Their `us_` spellings keep the US customary meaning.
+pub fn system_dependent_units(system: UnitSystem) -> Vec<&'static str> {
+    SYSTEM_VARIANTS
+        .iter()
+        .filter(|(term, variant_system, _)| {
+            *variant_system == system && find(term).is_some_and(|def| def.name == *term)
+        })
+        .map(|(term, _, _)| *term)
+        .collect()
+}
+
+/// Whether `unit` is written as explicitly US customary, as in us_mpg or "US gallons",
+/// which no unit system overrides.
+fn is_us_spelling(unit: &str) -> bool {
+    let unit_lower = unit.trim().to_lowercase();
+    unit_lower.starts_with("us_") || unit_lower.starts_with("us ")
+}
+
+/// Terms that name different units with no safe default, as `(term, candidates)`. Unless
+/// the unit system settles them through [`SYSTEM_VARIANTS`], or the other unit of a pair
+/// settles the category, they are answered with a suggestion rather than a guess.
+static AMBIGUOUS_TERMS: &[(&str, &[&str])] = &[
+    ("pt", &["pints", "points"]),
+    ("cwt", &["us_hundredweight", "uk_hundredweight"]),
+    ("hundredweight", &["us_hundredweight", "uk_hundredweight"]),
+    ("mb/s", &["megabits_per_second", "megabytes_per_second"]),
+];
+
+/// The error for `unit` when it is an ambiguous term that `system` does not settle to
+/// units of a single category, listing the units it may mean.
+pub fn disambiguate(unit: &str, system: Option<UnitSystem>) -> Option<String> {
+    let resolved = resolve_all(unit, system);
+    if resolved
+        .first()
+        .is_some_and(|first| resolved.iter().all(|def| def.unit_type == first.unit_type))
+    {
+        return None;
+    }
+
+    let unit_lower = unit.trim().to_lowercase();
+    let (_, candidates) = AMBIGUOUS_TERMS
+        .iter()
+        .find(|(term, _)| *term == unit_lower)?;
+    let candidates: Vec<&UnitDefinition> =
+        candidates.iter().filter_map(|name| find(name)).collect();
+    let suggestions: Vec<String> = candidates
+        .iter()
+        .map(|def| format!("{} ({})", def.name, def.conversion_summary()))
+        .collect();
+    // A system picks between variants of one category, not between categories, and one
+    // already set has had its say.
+    let settled_by_system = system.is_none()
+        && SYSTEM_VARIANTS
+            .iter()
+            .any(|(term, _, _)| *term == unit_lower)
+        && candidates
+            .iter()
+            .all(|def| def.unit_type == candidates[0].unit_type);
+    Some(format!(
+        "Ambiguous unit: {} could mean {}. Name one of them{}",
+        unit,
+        suggestions.join(" or "),
+        if settled_by_system {
+            ", or set a default unit system"
+        } else {
+            ""
+        }
+    ))
+}
+
+/// Like [`find_all`], but reads system-dependent terms the way `system` would.
+pub fn resolve_all(unit: &str, system: Option<UnitSystem>) -> Vec<&'static UnitDefinition> {
+    let Some(system) = system else {
+        return find_all(unit);
+    };
+    let variant = |term: &str| {
+        SYSTEM_VARIANTS
+            .iter()
+            .find(|(variant_term, variant_system, _)| {
+                *variant_system == system && *variant_term == term
+            })
+            .and_then(|(_, _, name)| find(name))
+    };
+
+    let matches = find_all(unit);
+    if matches.is_empty() {
+        return variant(&unit.trim().to_lowercase()).into_iter().collect();
+    }
+    if is_us_spelling(unit) {
+        return matches;
+    }
+    matches
+        .into_iter()
+        .map(|def| variant(def.name).unwrap_or(def))
+        .collect()
+}
+
+/// Resolves a source and target unit to definitions from the same category.
+///
+/// Fails with `Ambiguous unit: ...` when either names several units, such as `cwt`, with
+/// `Unsupported unit: <from>` when the source is unknown, and with
+/// `Unsupported unit: <to> for type: <category>` when the target does not belong to
+/// any category of the source.
+pub fn resolve_pair(
+    from_unit: &str,
+    to_unit: &str,
+    system: Option<UnitSystem>,
+) -> Result<(&'static UnitDefinition, &'static UnitDefinition)> {
+    // Terms spanning several categories, such as pt, are settled by pairing below.
+    if let Some(error) = [from_unit, to_unit]
+        .into_iter()
+        .filter(|unit| resolve_all(unit, system).is_empty())
+        .find_map(|unit| disambiguate(unit, system))
+    {
+        return Err(anyhow!("{}", error));
+    }
+
+    let sources = resolve_all(from_unit, system);
+    let Some(first_source) = sources.first() else {
+        return Err(anyhow!("Unsupported unit: {}", from_unit));
+    };
+
+    let targets = resolve_all(to_unit, system);
+    sources
+        .iter()
+        .find_map(|from| {
+            targets
+                .iter()
+                .find(|to| to.unit_type == from.unit_type)
+                .map(|to| (*from, *to))
+        })
+        .ok_or_else(|| {
+            anyhow!(
+                "Unsupported unit: {} for type: {}",
+                to_unit.to_lowercase(),
+                first_source.unit_type
+            )
+        })
+}
+
+/// Candidate targets for `auto` conversions as `(category, preferred by, units)`, with
+/// units ordered smallest first. A ladder preferred by no system is only used when the
+/// source unit already belongs to it.
+static HUMANIZE_LADDERS: &[(UnitType, &[UnitSystem], &[&str])] = &[
+    (
+        UnitType::Distance,
+        &[UnitSystem::Metric],
+        &["millimeters", "centimeters", "meters", "kilometers"],
+    ),
+    (
+        UnitType::Distance,
+        &[UnitSystem::Imperial, UnitSystem::Us],
+        &["inches", "feet", "miles"],
+    ),
+    (UnitType::Distance, &[], &["nautical_miles"]),
+    (
+        UnitType::Volume,
+        &[UnitSystem::Metric],
+        &["milliliters", "liters"],
+    ),
+    (
+        UnitType::Volume,
+        &[UnitSystem::Us],
+        &["fluid_ounces", "cups", "quarts", "gallons"],
+    ),
+    (
+        UnitType::Volume,
+        &[UnitSystem::Imperial],
+        &[
+            "imperial_fluid_ounces",
+            "imperial_pints",
+            "imperial_gallons",
+        ],
+    ),
+    (
+        UnitType::Weight,
+        &[UnitSystem::Metric],
+        &["grams", "kilograms"],
+    ),
+    (UnitType::Weight, &[UnitSystem::Us], &["ounces", "pounds"]),
+    (
+        UnitType::Weight,
+        &[UnitSystem::Imperial],
+        &["ounces", "pounds", "stones"],
+    ),
+    (
+        UnitType::Temperature,
+        &[UnitSystem::Metric, UnitSystem::Imperial],
+        &["celsius"],
+    ),
+    (UnitType::Temperature, &[UnitSystem::Us], &["fahrenheit"]),
+    (UnitType::Temperature, &[], &["kelvin"]),
+    (
+        UnitType::Digital,
+        &[UnitSystem::Metric, UnitSystem::Imperial, UnitSystem::Us],
+        &["bytes", "kilobytes", "megabytes", "gigabytes", "terabytes"],
+    ),
+    (
+        UnitType::Pressure,
+        &[UnitSystem::Metric],
+        &["pascal", "kilopascal", "megapascal"],
+    ),
+    (
+        UnitType::Pressure,
+        &[UnitSystem::Imperial, UnitSystem::Us],
+        &["psi"],
+    ),
+    (
+        UnitType::Speed,
+        &[UnitSystem::Metric],
+        &["kilometers_per_hour"],
+    ),
+    (
+        UnitType::Speed,
+        &[UnitSystem::Imperial, UnitSystem::Us],
+        &["miles_per_hour"],
+    ),
+    (UnitType::Speed, &[], &["knots"]),
+    (
+        UnitType::DataRate,
+        &[UnitSystem::Metric, UnitSystem::Imperial, UnitSystem::Us],
+        &[
+            "bits_per_second",
+            "kilobits_per_second",
+            "megabits_per_second",
+            "gigabits_per_second",
+        ],
+    ),
+    (
+        UnitType::Time,
+        &[UnitSystem::Metric, UnitSystem::Imperial, UnitSystem::Us],
+        &["seconds", "minutes", "hours", "days"],
+    ),
+];
+
+/// Picks the most readable unit in `from`'s category for `value` (expressed in `from`).
+///
+/// The ladder is the one preferred by `system` when given, otherwise the one the source
+/// unit belongs to, otherwise the category's first. Within it, the largest unit that
+/// keeps the magnitude at or above one wins. Categories without ladders keep the source.
+pub fn humanize(
+    from: &'static UnitDefinition,
+    value: f64,
+    system: Option<UnitSystem>,
+    context: &ConversionContext,
+) -> (&'static UnitDefinition, f64) {
+    let ladders: Vec<&[&str]> = HUMANIZE_LADDERS
+        .iter()
+        .filter(|(unit_type, _, _)| *unit_type == from.unit_type)
+        .map(|(_, _, ladder)| *ladder)
+        .collect();
+
+    let preferred = system.and_then(|system| {
+        HUMANIZE_LADDERS
+            .iter()
+            .find(|(unit_type, systems, _)| {
+                *unit_type == from.unit_type && systems.contains(&system)
+            })
+            .map(|(_, _, ladder)| *ladder)
+    });
+
+    let Some(ladder) = preferred
+        .or_else(|| {
+            ladders
+                .iter()
+                .copied()
+                .find(|ladder| ladder.contains(&from.name))
+        })
+        .or_else(|| ladders.first().copied())
+    else {
+        return (from, value);
+    };
+
+    let base_value = from.conversion.to_base(value, context);
+    let candidates: Vec<(&'static UnitDefinition, f64)> = ladder
+        .iter()
+        .filter_map(|name| {
+            units_of(from.unit_type)
+                .find(|def| def.name == *name)
+                .map(|def| (def, def.conversion.from_base(base_value, context)))
+        })
+        .collect();
+
+    candidates
+        .iter()
+        .rev()
+        .find(|(_, converted)| converted.abs() >= 1.0)
+        .or(candidates.first())
+        .copied()
+        .unwrap_or((from, value))
+}
+
+/// Formats `value` with three significant digits, dropping trailing zeros.
+pub fn format_readable(value: f64) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return value.to_string();
+    }
+
+    let magnitude = value.abs().log10().floor() as i32;
+    let decimals = (2 - magnitude).max(0) as usize;
+    let formatted = format!("{:.*}", decimals, value);
+    if formatted.contains('.') {
+        formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        formatted
+    }
+}
+
+/// Units of one category ordered by canonical name, independent of registration order.
+pub fn units_of(unit_type: UnitType) -> impl Iterator<Item = &'static UnitDefinition> {
+    let mut units: Vec<&'static UnitDefinition> = registry()
+        .iter()
+        .copied()
+        .filter(|def| def.unit_type == unit_type)
+        .collect();
+    units.sort_by_key(|def| def.name);
+    units.into_iter()
+}
+
+/// Every unit ordered by category, then canonical name. All listings emitted to clients
+/// follow this order so they stay stable between releases.
+pub fn catalog() -> impl Iterator<Item = &'static UnitDefinition> {
+    UnitType::ALL.into_iter().flat_map(units_of)
+}
+
+/// Categories with at least one registered unit, in [`catalog`] order. Time and energy
+/// only have units while the scientific set is enabled.
+pub fn categories() -> Vec<UnitType> {
+    UnitType::ALL
+        .into_iter()
+        .filter(|unit_type| units_of(*unit_type).next().is_some())
+        .collect()
+}
+
+/// Position of `category` in [`catalog`] order, so units from other sources such as
+/// plugins merge into the same listing. Sorting by `(rank, category, name)` puts
+/// categories that are not a [`UnitType`] after the built-in ones, alphabetically.
+pub fn category_rank(category: &str) -> usize {
+    UnitType::ALL
+        .iter()
+        .position(|unit_type| unit_type.to_string() == category)
+        .unwrap_or(UnitType::ALL.len())
+}
+
+pub fn unit_names(unit_type: UnitType) -> Vec<&'static str> {
+    units_of(unit_type).map(|def| def.name).collect()
+}
+
+fn beaufort_to_mps(beaufort: f64) -> f64 {
+    match beaufort as i32 {
+        0 => 0.0,
+        1 => 1.5,
+        2 => 3.0,
+        3 => 5.0,
+        4 => 7.5,
+        5 => 10.0,
+        6 => 12.5,
+        7 => 15.5,
+        8 => 18.5,
+        9 => 22.0,
+        10 => 26.0,
+        11 => 30.0,
+        12 => 35.0,
+        _ => 35.0, // Cap at hurricane force
+    }
+}
+
+fn mps_to_beaufort(mps: f64) -> f64 {
+    if mps < 0.5 {
+        0.0
+    } else if mps < 2.0 {
+        1.0
+    } else if mps < 4.0 {
+        2.0
+    } else if mps < 6.0 {
+        3.0
+    } else if mps < 9.0 {
+        4.0
+    } else if mps < 11.0 {
+        5.0
+    } else if mps < 14.0 {
+        6.0
+    } else if mps < 17.0 {
+        7.0
+    } else if mps < 21.0 {
+        8.0
+    } else if mps < 24.0 {
+        9.0
+    } else if mps < 28.0 {
+        10.0
+    } else if mps < 33.0 {
+        11.0
+    } else {
+        12.0
+    }
+}
+
+/// Lowest kinematic viscosity, about 32 SUS, covered by the ASTM D2161 correlation.
+const SAYBOLT_MIN_CENTISTOKES: f64 = 1.81;
+
+/// Saybolt Universal Seconds at 100 °F for a kinematic viscosity in centistokes, per the
+/// ASTM D2161 correlation. Viscosities below its range have no equivalent (NaN).
+fn centistokes_to_sus(centistokes: f64) -> f64 {
+    if centistokes.is_nan() || centistokes < SAYBOLT_MIN_CENTISTOKES {
+        return f64::NAN;
+    }
+
+    4.6324 * centistokes
+        + (1.0 + 0.03264 * centistokes)
+            / ((3930.2
+                + 262.7 * centistokes
+                + 23.97 * centistokes.powi(2)
+                + 1.646 * centistokes.powi(3))
+                * 1e-5)
+}
+
+/// Inverse of [`centistokes_to_sus`]. The correlation has no closed-form inverse, so this
+/// bisects it; it is increasing and never below 4.6324 SUS per centistoke.
+fn sus_to_centistokes(sus: f64) -> f64 {
+    if sus.is_nan() || sus < centistokes_to_sus(SAYBOLT_MIN_CENTISTOKES) {
+        return f64::NAN;
+    }
+
+    let (mut low, mut high) = (
+        SAYBOLT_MIN_CENTISTOKES,
+        (sus / 4.6324).max(SAYBOLT_MIN_CENTISTOKES),
+    );
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        if centistokes_to_sus(middle) < sus {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+const KIB: f64 = 1024.0;
+const MILE: f64 = 1609.344;
+const NAUTICAL_MILE: f64 = 1852.0;
+/// The exact US gallon in liters, for derived units; the gallons entry keeps its
+/// published rounding.
+const US_GALLON: f64 = 3.785_411_784;
+const IMPERIAL_GALLON: f64 = 4.546_09;
+
+pub static UNITS: &[UnitDefinition] = &[
+    // Distance units (to meters)
+    UnitDefinition {
+        name: "meters",
+        symbol: "m",
+        aliases: &["m"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(1.0),
+        description: "SI base unit of length, defined since 1983 by the distance light travels in 1/299,792,458 of a second. Used for nearly all everyday and scientific measurement outside the US.",
+    },
+    UnitDefinition {
+        name: "millimeters",
+        symbol: "mm",
+        aliases: &["mm"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(0.001),
+        description: "One thousandth of a meter. Used for engineering drawings, rainfall and small parts.",
+    },
+    UnitDefinition {
+        name: "centimeters",
+        symbol: "cm",
+        aliases: &["cm"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(0.01),
+        description: "One hundredth of a meter. Used for body height, clothing sizes and everyday objects.",
+    },
+    UnitDefinition {
+        name: "kilometers",
+        symbol: "km",
+        aliases: &["km"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(1000.0),
+        description: "One thousand meters. The standard unit for road distances and travel in metric countries.",
+    },
+    UnitDefinition {
+        name: "miles",
+        symbol: "mi",
+        aliases: &["mi"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(MILE),
+        description: "The statute mile of 5,280 feet, descended from the Roman thousand paces. Used for road distances in the US and the UK.",
+    },
+    UnitDefinition {
+        name: "feet",
+        symbol: "ft",
+        aliases: &["ft"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(0.3048),
+        description: "The international foot of 12 inches, fixed at exactly 0.3048 m in 1959. Common for human height, building dimensions and aircraft altitude.",
+    },
+    UnitDefinition {
+        name: "inches",
+        symbol: "in",
+        aliases: &["in"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(0.0254),
+        description: "One twelfth of a foot, exactly 25.4 mm. Used for screen sizes, tools, lumber and small US customary measurements.",
+    },
+    UnitDefinition {
+        name: "hands",
+        symbol: "hh",
+        aliases: &["hh", "hand"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(0.1016),
+        description: "Four inches, exactly 101.6 mm, the traditional measure of a horse's height at the withers. Values are read as decimal hands, so pass 15 hands 2 inches as 15.5; the mixed format writes results the traditional way, as 15.2 hh.",
+    },
+    UnitDefinition {
+        name: "yards",
+        symbol: "yd",
+        aliases: &["yd"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(0.9144),
+        description: "Three feet, exactly 0.9144 m. Found in sports fields, fabric and golf distances.",
+    },
+    UnitDefinition {
+        name: "nautical_miles",
+        symbol: "nmi",
+        aliases: &["nmi"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(NAUTICAL_MILE),
+        description: "Originally one minute of latitude, now exactly 1,852 m. The unit of distance for marine and air navigation.",
+    },
+    UnitDefinition {
+        name: "fathoms",
+        symbol: "ftm",
+        aliases: &["ftm", "fathom"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(1.8288),
+        description: "Six feet, exactly 1.8288 m, originally the span of a sailor's outstretched arms. Used for water depth on nautical charts and for anchor chain and fishing line.",
+    },
+    UnitDefinition {
+        name: "flight_levels",
+        symbol: "FL",
+        aliases: &["fl", "flight_level"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(30.48),
+        description: "Hundreds of feet of pressure altitude, so FL350 is 35,000 ft. Flight levels are read against the standard pressure of 1013.25 hPa, so they match true altitude above sea level only when the local pressure is standard.",
+    },
+    UnitDefinition {
+        name: "meters_msl",
+        symbol: "m MSL",
+        aliases: &["m_msl", "meters_amsl", "m_amsl", "msl"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(1.0),
+        description: "Meters of altitude above mean sea level. Used for terrain and airfield elevations, summit heights and altitudes in metric aviation.",
+    },
+    UnitDefinition {
+        name: "rack_units",
+        symbol: "U",
+        aliases: &["ru", "rack_unit"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(0.04445),
+        description: "The height of one slot in a 19-inch equipment rack, 1.75 inches (44.45 mm) as set by EIA-310. Servers and switches are sized in whole units, such as 1U or 2U, and a full-height rack is usually 42U.",
+    },
+    // Volume units (to liters)
+    UnitDefinition {
+        name: "liters",
+        symbol: "L",
+        aliases: &["l"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(1.0),
+        description: "One cubic decimeter. The everyday metric unit for drinks, fuel and engine displacement.",
+    },
+    UnitDefinition {
+        name: "milliliters",
+        symbol: "mL",
+        aliases: &["ml"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(1.0 / 1000.0),
+        description: "One thousandth of a liter, equal to a cubic centimeter. Used for medicine doses, recipes and small containers.",
+    },
+    UnitDefinition {
+        name: "gallons",
+        symbol: "gal",
+        aliases: &["gal", "us_gallons", "us_gallon", "us_gal"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(3.78541),
+        description: "The US liquid gallon of 231 cubic inches, derived from the old English wine gallon. Used for fuel and milk in the US.",
+    },
+    UnitDefinition {
+        name: "quarts",
+        symbol: "qt",
+        aliases: &["qt", "us_quarts", "us_quart", "us_qt"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(0.946353),
+        description: "A quarter of a US gallon. Common for motor oil, milk and cooking in the US.",
+    },
+    UnitDefinition {
+        name: "pints",
+        symbol: "pt",
+        aliases: &["pt", "us_pints", "us_pint", "us_pt"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(0.473176),
+        description: "An eighth of a US gallon. Used for beverages, ice cream and cooking in the US.",
+    },
+    UnitDefinition {
+        name: "cups",
+        symbol: "cup",
+        aliases: &["us_cups", "us_cup"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(0.236588),
+        description: "The US customary cup of half a pint. A staple of American recipes.",
+    },
+    UnitDefinition {
+        name: "fluid_ounces",
+        symbol: "fl oz",
+        aliases: &["fl_oz", "us_fluid_ounces", "us_fluid_ounce", "us_fl_oz"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(0.0295735),
+        description: "One 128th of a US gallon. Used for beverage cans, bottles and cosmetics labelling in the US.",
+    },
+    UnitDefinition {
+        name: "imperial_gallons",
+        symbol: "imp gal",
+        aliases: &["imp_gal"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(IMPERIAL_GALLON),
+        description: "The British imperial gallon, defined in 1824 as the volume of ten pounds of water and now exactly 4.54609 L. Still used for fuel economy figures in the UK.",
+    },
+    UnitDefinition {
+        name: "imperial_quarts",
+        symbol: "imp qt",
+        aliases: &["imp_qt"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(1.1365225),
+        description: "A quarter of an imperial gallon, about 20% larger than the US quart. Found in older British recipes.",
+    },
+    UnitDefinition {
+        name: "imperial_pints",
+        symbol: "imp pt",
+        aliases: &["imp_pt"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(0.56826125),
+        description: "An eighth of an imperial gallon, or 20 imperial fluid ounces. The legal measure for draught beer and cider in the UK.",
+    },
+    UnitDefinition {
+        name: "imperial_fluid_ounces",
+        symbol: "imp fl oz",
+        aliases: &["imp_fl_oz"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(0.0284130625),
+        description: "One 160th of an imperial gallon, slightly smaller than the US fluid ounce. Used in British recipes and spirits measures.",
+    },
+    UnitDefinition {
+        name: "metric_cups",
+        symbol: "metric cup",
+        aliases: &[],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(0.25),
+        description: "A cup of exactly 250 mL, used in recipes from Australia, New Zealand, Canada and South Africa.",
+    },
+    // Weight units (to kilograms)
+    UnitDefinition {
+        name: "kilograms",
+        symbol: "kg",
+        aliases: &["kg"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(1.0),
+        description: "SI base unit of mass, defined since 2019 via the Planck constant. Used for body weight, groceries and freight worldwide.",
+    },
+    UnitDefinition {
+        name: "grams",
+        symbol: "g",
+        aliases: &["g"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(1.0 / 1000.0),
+        description: "One thousandth of a kilogram. Used for recipes, nutrition labels and small packages.",
+    },
+    UnitDefinition {
+        name: "pounds",
+        symbol: "lb",
+        aliases: &["lb", "lbs"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(0.453592),
+        description: "The avoirdupois pound of 16 ounces, fixed at 0.45359237 kg in 1959. Used for body weight and groceries in the US.",
+    },
+    UnitDefinition {
+        name: "ounces",
+        symbol: "oz",
+        aliases: &["oz"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(0.0283495),
+        description: "One sixteenth of an avoirdupois pound. Used for food portions and postal weights in the US.",
+    },
+    UnitDefinition {
+        name: "stones",
+        symbol: "st",
+        aliases: &["st"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(6.35029),
+        description: "Fourteen pounds, a traditional English unit. Still used for body weight in the UK and Ireland.",
+    },
+    UnitDefinition {
+        name: "us_hundredweight",
+        symbol: "cwt (US)",
+        aliases: &["us_cwt", "short_hundredweight", "cental"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(45.359_237),
+        description: "The short hundredweight of 100 lb, exactly 45.359237 kg. Used for grain, livestock and other agricultural commodity prices in the US and Canada.",
+    },
+    UnitDefinition {
+        name: "uk_hundredweight",
+        symbol: "cwt (UK)",
+        aliases: &["uk_cwt", "long_hundredweight", "imperial_hundredweight"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(50.802_345_44),
+        description: "The long hundredweight of 112 lb (eight stone), exactly 50.80234544 kg. A twentieth of a long ton, still found on British farm sacks and coal deliveries.",
+    },
+    UnitDefinition {
+        name: "troy_ounces",
+        symbol: "oz t",
+        aliases: &["ozt", "troy_oz"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(0.0311034768),
+        description: "The troy ounce of 480 grains, about 10% heavier than the avoirdupois ounce, named after the French market town of Troyes. The standard unit for pricing gold, silver and platinum.",
+    },
+    UnitDefinition {
+        name: "troy_pounds",
+        symbol: "lb t",
+        aliases: &["troy_lb"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(0.3732417216),
+        description: "Twelve troy ounces, lighter than the avoirdupois pound despite its heavier ounces. Historically used by apothecaries and mints.",
+    },
+    UnitDefinition {
+        name: "grains",
+        symbol: "gr",
+        aliases: &["gr"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(0.00006479891),
+        description: "Originally the weight of a barleycorn, now exactly 64.79891 mg and shared by the troy, apothecary and avoirdupois systems. Used for bullets, arrows and medication doses.",
+    },
+    UnitDefinition {
+        name: "carats",
+        symbol: "ct",
+        aliases: &["ct"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(0.0002),
+        description: "The metric carat of exactly 200 mg, adopted in 1907 and derived from the carob seed. The unit for weighing gemstones and pearls; unrelated to the karat measure of gold purity.",
+    },
+    UnitDefinition {
+        name: "pennyweights",
+        symbol: "dwt",
+        aliases: &["dwt"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(0.00155517384),
+        description: "Twenty-four grains, or 1/20 troy ounce, once the weight of a silver penny. Still used by jewellers to weigh precious metal.",
+    },
+    // Temperature units (to celsius)
+    UnitDefinition {
+        name: "celsius",
+        symbol: "°C",
+        aliases: &["c"],
+        unit_type: UnitType::Temperature,
+        conversion: Conversion::Linear(1.0),
+        description: "Named after Anders Celsius (1742); water freezes at 0 and boils at 100 at sea level. The everyday scale in most of the world.",
+    },
+    UnitDefinition {
+        name: "fahrenheit",
+        symbol: "°F",
+        aliases: &["f"],
+        unit_type: UnitType::Temperature,
+        conversion: Conversion::Affine {
+            offset: 32.0,
+            numerator: 5.0,
+            denominator: 9.0,
+        },
+        description: "Proposed by Daniel Gabriel Fahrenheit (1724); water freezes at 32 and boils at 212. The everyday scale in the US.",
+    },
+    UnitDefinition {
+        name: "kelvin",
+        symbol: "K",
+        aliases: &["k"],
+        unit_type: UnitType::Temperature,
+        conversion: Conversion::Affine {
+            offset: 273.15,
+            numerator: 1.0,
+            denominator: 1.0,
+        },
+        description: "SI base unit of thermodynamic temperature, starting at absolute zero with Celsius-sized steps. Used in science and engineering.",
+    },
+    // Digital units (to bytes)
+    UnitDefinition {
+        name: "bytes",
+        symbol: "B",
+        aliases: &["b"],
+        unit_type: UnitType::Digital,
+        conversion: Conversion::Linear(1.0),
+        description: "Eight bits, the smallest addressable unit of memory on modern computers.",
+    },
+    UnitDefinition {
+        name: "kilobytes",
+        symbol: "KB",
+        aliases: &["kb"],
+        unit_type: UnitType::Digital,
+        conversion: Conversion::Linear(KIB),
+        description: "1,024 bytes in the binary convention used here. Typical for small documents and configuration files.",
+    },
+    UnitDefinition {
+        name: "megabytes",
+        symbol: "MB",
+        aliases: &["mb"],
+        unit_type: UnitType::Digital,
+        conversion: Conversion::Linear(KIB * KIB),
+        description: "1,024 kilobytes. Typical for photos, songs and application downloads.",
+    },
+    UnitDefinition {
+        name: "gigabytes",
+        symbol: "GB",
+        aliases: &["gb"],
+        unit_type: UnitType::Digital,
+        conversion: Conversion::Linear(KIB * KIB * KIB),
+        description: "1,024 megabytes. Used for memory, phone storage and video files.",
+    },
+    UnitDefinition {
+        name: "terabytes",
+        symbol: "TB",
+        aliases: &["tb"],
+        unit_type: UnitType::Digital,
+        conversion: Conversion::Linear(KIB * KIB * KIB * KIB),
+        description: "1,024 gigabytes. Used for hard drives, backups and datasets.",
+    },
+    UnitDefinition {
+        name: "bits",
+        symbol: "bit",
+        aliases: &[],
+        unit_type: UnitType::Digital,
+        conversion: Conversion::Linear(1.0 / 8.0),
+        description: "A single binary digit, coined by John Tukey in 1947. The fundamental unit of information.",
+    },
+    UnitDefinition {
+        name: "kilobits",
+        symbol: "kbit",
+        aliases: &["kbit"],
+        unit_type: UnitType::Digital,
+        conversion: Conversion::Linear(KIB / 8.0),
+        description: "1,024 bits. Appears in memory chip capacities and older networking literature.",
+    },
+    UnitDefinition {
+        name: "megabits",
+        symbol: "Mbit",
+        aliases: &["mbit"],
+        unit_type: UnitType::Digital,
+        conversion: Conversion::Linear(KIB * KIB / 8.0),
+        description: "1,024 kilobits. Appears in memory chip capacities and media bitrate budgets.",
+    },
+    UnitDefinition {
+        name: "gigabits",
+        symbol: "Gbit",
+        aliases: &["gbit"],
+        unit_type: UnitType::Digital,
+        conversion: Conversion::Linear(KIB * KIB * KIB / 8.0),
+        description: "1,024 megabits. Appears in DRAM and flash chip capacities.",
+    },
+    // Pressure units (to pascal)
+    UnitDefinition {
+        name: "pascal",
+        symbol: "Pa",
+        aliases: &["pa"],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(1.0),
+        description: "SI unit of pressure, one newton per square meter, named after Blaise Pascal. Used in science and, via hectopascals, in meteorology.",
+    },
+    UnitDefinition {
+        name: "kilopascal",
+        symbol: "kPa",
+        aliases: &["kpa"],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(1000.0),
+        description: "One thousand pascals. Used for tyre pressures and building services in metric countries.",
+    },
+    UnitDefinition {
+        name: "megapascal",
+        symbol: "MPa",
+        aliases: &["mpa"],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(1_000_000.0),
+        description: "One million pascals. Used for material strength and hydraulic system ratings.",
+    },
+    UnitDefinition {
+        name: "bar",
+        symbol: "bar",
+        aliases: &[],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(100_000.0),
+        description: "Exactly 100,000 Pa, roughly atmospheric pressure at sea level, introduced by Vilhelm Bjerknes. Used for diving, espresso machines and European tyre gauges.",
+    },
+    UnitDefinition {
+        name: "psi",
+        symbol: "psi",
+        aliases: &[],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(6894.76),
+        description: "Pound-force per square inch, the imperial pressure unit. Used for tyre pressures, compressors and scuba tanks in the US.",
+    },
+    UnitDefinition {
+        name: "atmosphere",
+        symbol: "atm",
+        aliases: &["atm"],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(101_325.0),
+        description: "Standard atmospheric pressure at sea level, defined as 101,325 Pa. Used in chemistry and for describing depth pressure in diving.",
+    },
+    UnitDefinition {
+        name: "torr",
+        symbol: "Torr",
+        aliases: &[],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(133.322),
+        description: "1/760 of a standard atmosphere, named after Evangelista Torricelli. Used in vacuum technology.",
+    },
+    UnitDefinition {
+        name: "mmhg",
+        symbol: "mmHg",
+        aliases: &[],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(133.322),
+        description: "The pressure of a one millimeter column of mercury, practically equal to the torr. Used for blood pressure readings.",
+    },
+    UnitDefinition {
+        name: "hectopascals",
+        symbol: "hPa",
+        aliases: &["hpa", "hectopascal"],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(100.0),
+        description: "One hundred pascals, numerically identical to the millibar. The unit of sea-level pressure in weather reports and aviation QNH settings outside North America.",
+    },
+    UnitDefinition {
+        name: "millibars",
+        symbol: "mbar",
+        aliases: &["mbar", "millibar"],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(100.0),
+        description: "One thousandth of a bar, equal to one hectopascal. The traditional meteorological pressure unit, still common on barometers and older weather charts.",
+    },
+    UnitDefinition {
+        name: "inches_of_mercury",
+        symbol: "inHg",
+        aliases: &["inhg", "in_hg"],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(3_386.389),
+        description: "The pressure of a one inch column of mercury at 0 °C. Used for altimeter settings and barometric pressure in US weather reports.",
+    },
+    UnitDefinition {
+        name: "technical_atmosphere",
+        symbol: "at",
+        aliases: &["kgf/cm²", "kgf/cm2", "technical_atmospheres"],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(98_066.5),
+        description: "One kilogram-force per square centimeter, exactly 98,066.5 Pa. Found on older European and Asian compressors, boilers and HVAC nameplates.",
+    },
+    UnitDefinition {
+        name: "inches_of_water",
+        symbol: "inH2O",
+        aliases: &["inh2o", "in_h2o", "iwc", "inches_of_water_column"],
+        unit_type: UnitType::Pressure,
+        conversion: Conversion::Linear(249.088_91),
+        description: "The pressure of a one inch column of water (conventional, 1,000 kg/m³ under standard gravity). Used for duct static pressure, gas appliance manifolds and filter drop on HVAC equipment labels.",
+    },
+    // Speed units (to meters per second)
+    UnitDefinition {
+        name: "meters_per_second",
+        symbol: "m/s",
+        aliases: &["mps", "m/s"],
+        unit_type: UnitType::Speed,
+        conversion: Conversion::Linear(1.0),
+        description: "SI unit of speed. Used in physics and for wind speeds in many weather services.",
+    },
+    UnitDefinition {
+        name: "kilometers_per_hour",
+        symbol: "km/h",
+        aliases: &["kph", "km/h"],
+        unit_type: UnitType::Speed,
+        conversion: Conversion::Linear(1.0 / 3.6),
+        description: "Kilometers travelled in one hour. The road speed unit in metric countries.",
+    },
+    UnitDefinition {
+        name: "miles_per_hour",
+        symbol: "mph",
+        aliases: &["mph"],
+        unit_type: UnitType::Speed,
+        conversion: Conversion::Linear(0.44704),
+        description: "Statute miles travelled in one hour. The road speed unit in the US and the UK.",
+    },
+    UnitDefinition {
+        name: "knots",
+        symbol: "kn",
+        aliases: &["kt"],
+        unit_type: UnitType::Speed,
+        conversion: Conversion::Linear(0.514444),
+        description: "One nautical mile per hour, named after the knotted log line sailors used to measure speed. Used in marine and aviation navigation.",
+    },
+    UnitDefinition {
+        name: "feet_per_second",
+        symbol: "ft/s",
+        aliases: &["fps", "ft/s"],
+        unit_type: UnitType::Speed,
+        conversion: Conversion::Linear(0.3048),
+        description: "Feet travelled in one second. Used in US engineering and ballistics.",
+    },
+    UnitDefinition {
+        name: "beaufort",
+        symbol: "Bft",
+        aliases: &[],
+        unit_type: UnitType::Speed,
+        conversion: Conversion::Custom {
+            to_base: beaufort_to_mps,
+            from_base: mps_to_beaufort,
+        },
+        description: "Empirical wind force scale from 0 (calm) to 12 (hurricane) devised by Francis Beaufort in 1805. Converts to a representative speed for each force and back to the force band containing a speed.",
+    },
+    // Data rate units (to bits per second)
+    UnitDefinition {
+        name: "bits_per_second",
+        symbol: "bps",
+        aliases: &["bps", "bit/s"],
+        unit_type: UnitType::DataRate,
+        conversion: Conversion::Linear(1.0),
+        description: "One bit transferred per second, the base unit of data rate. Used for serial links and low-speed telemetry.",
+    },
+    UnitDefinition {
+        name: "kilobits_per_second",
+        symbol: "kbps",
+        aliases: &["kbps", "kbit/s"],
+        unit_type: UnitType::DataRate,
+        conversion: Conversion::Linear(1_000.0),
+        description: "One thousand bits per second. Used for audio bitrates and dial-up era connection speeds.",
+    },
+    UnitDefinition {
+        name: "megabits_per_second",
+        symbol: "Mbps",
+        aliases: &["mbps", "mbit/s"],
+        unit_type: UnitType::DataRate,
+        conversion: Conversion::Linear(1_000_000.0),
+        description: "One million bits per second. The unit ISPs advertise broadband and mobile speeds in; note the lowercase b for bits.",
+    },
+    UnitDefinition {
+        name: "gigabits_per_second",
+        symbol: "Gbps",
+        aliases: &["gbps", "gbit/s"],
+        unit_type: UnitType::DataRate,
+        conversion: Conversion::Linear(1_000_000_000.0),
+        description: "One billion bits per second. Used for fibre connections, Ethernet ports and data center links.",
+    },
+    UnitDefinition {
+        name: "bytes_per_second",
+        symbol: "B/s",
+        aliases: &["byte/s"],
+        unit_type: UnitType::DataRate,
+        conversion: Conversion::Linear(8.0),
+        description: "One byte, eight bits, transferred per second. The unit download managers and disk benchmarks build on.",
+    },
+    UnitDefinition {
+        name: "megabytes_per_second",
+        symbol: "MB/s",
+        aliases: &["mbyte/s"],
+        unit_type: UnitType::DataRate,
+        conversion: Conversion::Linear(8_000_000.0),
+        description: "One million bytes per second. Shown by browsers and download managers, and used for disk and USB throughput; 100 Mbps is 12.5 MB/s.",
+    },
+    // Typography units (to points)
+    UnitDefinition {
+        name: "points",
+        symbol: "pt",
+        aliases: &["pt"],
+        unit_type: UnitType::Typography,
+        conversion: Conversion::Linear(1.0),
+        description: "The desktop publishing (PostScript) point of exactly 1/72 inch, popularised by Adobe and Apple in the 1980s. The standard unit for font sizes.",
+    },
+    UnitDefinition {
+        name: "picas",
+        symbol: "pc",
+        aliases: &["pc"],
+        unit_type: UnitType::Typography,
+        conversion: Conversion::Linear(12.0),
+        description: "Twelve points, or 1/6 inch. Used for column widths and page layout grids.",
+    },
+    UnitDefinition {
+        name: "pixels",
+        symbol: "px",
+        aliases: &["px"],
+        unit_type: UnitType::Typography,
+        conversion: Conversion::Contextual(|context| 72.0 / context.dpi),
+        description: "One device pixel at the requested dpi (96 by default, the CSS reference pixel). Used for screen layouts and web design.",
+    },
+    UnitDefinition {
+        name: "ems",
+        symbol: "em",
+        aliases: &["em", "rem"],
+        unit_type: UnitType::Typography,
+        conversion: Conversion::Contextual(|context| context.base_font_size * 72.0 / context.dpi),
+        description: "The current font size, originally the width of a capital M. Sized from the requested base font size in pixels (16 by default, the browser default), so ems convert like CSS rem.",
+    },
+    UnitDefinition {
+        name: "millimeters",
+        symbol: "mm",
+        aliases: &["mm"],
+        unit_type: UnitType::Typography,
+        conversion: Conversion::Linear(72.0 / 25.4),
+        description: "One thousandth of a meter. Used for print margins, paper sizes and bleed.",
+    },
+    UnitDefinition {
+        name: "inches",
+        symbol: "in",
+        aliases: &["in"],
+        unit_type: UnitType::Typography,
+        conversion: Conversion::Linear(72.0),
+        description: "Exactly 72 points. Used for print dimensions and to define screen density in dots per inch.",
+    },
+    // Level units (to power ratio against the reference power)
+    UnitDefinition {
+        name: "power_ratio",
+        symbol: "P/Pref",
+        aliases: &[],
+        unit_type: UnitType::Level,
+        conversion: Conversion::Linear(1.0),
+        description: "Plain ratio of a power to the reference power. A ratio of 2 is about 3 dB; used for amplifier gain and attenuation before taking logarithms.",
+    },
+    UnitDefinition {
+        name: "amplitude_ratio",
+        symbol: "A/Aref",
+        aliases: &["voltage_ratio"],
+        unit_type: UnitType::Level,
+        conversion: Conversion::Custom {
+            to_base: |ratio| ratio * ratio,
+            from_base: f64::sqrt,
+        },
+        description: "Ratio of a field quantity such as voltage, current or sound pressure to its reference. Power scales with the square of amplitude, so an amplitude ratio of 2 is about 6 dB.",
+    },
+    UnitDefinition {
+        name: "decibels",
+        symbol: "dB",
+        aliases: &["db"],
+        unit_type: UnitType::Level,
+        conversion: Conversion::Logarithmic {
+            multiplier: 10.0,
+            reference_watts: None,
+        },
+        description: "One tenth of a bel, named after Alexander Graham Bell: ten times the base-10 logarithm of a power ratio. Used for gain, loss and loudness throughout acoustics and electronics.",
+    },
+    UnitDefinition {
+        name: "decibel_milliwatts",
+        symbol: "dBm",
+        aliases: &["dbm"],
+        unit_type: UnitType::Level,
+        conversion: Conversion::Logarithmic {
+            multiplier: 10.0,
+            reference_watts: Some(0.001),
+        },
+        description: "Power level in decibels relative to one milliwatt, so 0 dBm is 1 mW and 30 dBm is 1 W. Standard for radio transmitter output, receiver sensitivity and fibre optic power.",
+    },
+    UnitDefinition {
+        name: "decibel_watts",
+        symbol: "dBW",
+        aliases: &["dbw"],
+        unit_type: UnitType::Level,
+        conversion: Conversion::Logarithmic {
+            multiplier: 10.0,
+            reference_watts: Some(1.0),
+        },
+        description: "Power level in decibels relative to one watt, 30 dB below dBm. Used for satellite link budgets and broadcast transmitter power.",
+    },
+    UnitDefinition {
+        name: "watts",
+        symbol: "W",
+        aliases: &["watt"],
+        unit_type: UnitType::Level,
+        conversion: Conversion::Contextual(|context| 1.0 / context.reference),
+        description: "Absolute power in watts, so 1 W is 30 dBm and 0 dBW. Converting to relative decibels compares against the reference power.",
+    },
+    UnitDefinition {
+        name: "milliwatts",
+        symbol: "mW",
+        aliases: &["milliwatt"],
+        unit_type: UnitType::Level,
+        conversion: Conversion::Contextual(|context| 0.001 / context.reference),
+        description: "One thousandth of a watt, the power behind 0 dBm. Typical of Wi-Fi and Bluetooth transmitters, laser pointers and optical links. Only the exact symbol mW is accepted, since MW is a megawatt.",
+    },
+    // Slope units (to rise over run)
+    UnitDefinition {
+        name: "slope_ratio",
+        symbol: "rise/run",
+        aliases: &["rise_over_run", "gradient"],
+        unit_type: UnitType::Slope,
+        conversion: Conversion::Linear(1.0),
+        description: "Vertical rise divided by horizontal run, so 0.01 climbs one meter every hundred. The plain form engineers calculate with.",
+    },
+    UnitDefinition {
+        name: "percent_grade",
+        symbol: "%",
+        aliases: &["percent", "grade"],
+        unit_type: UnitType::Slope,
+        conversion: Conversion::Linear(0.01),
+        description: "Rise per hundred of run. Used on road signs and for ramps and driveways; a 6% grade is steep for a highway.",
+    },
+    UnitDefinition {
+        name: "per_mille_grade",
+        symbol: "‰",
+        aliases: &["per_mille", "permille", "per_mil"],
+        unit_type: UnitType::Slope,
+        conversion: Conversion::Linear(0.001),
+        description: "Rise per thousand of run. The usual railway gradient in continental Europe, where mainlines rarely exceed 25‰ and high-speed lines 35‰.",
+    },
+    UnitDefinition {
+        name: "one_in_n",
+        symbol: "1 in N",
+        aliases: &["1_in_n", "1:n", "one_in"],
+        unit_type: UnitType::Slope,
+        conversion: Conversion::Custom {
+            to_base: |run| 1.0 / run,
+            from_base: |ratio| 1.0 / ratio,
+        },
+        description: "The horizontal run over which the line rises by one, so 1 in 40 is 25‰. Traditional on British and Commonwealth railway gradient posts; larger numbers are gentler.",
+    },
+    UnitDefinition {
+        name: "slope_degrees",
+        symbol: "° slope",
+        aliases: &["degrees_of_slope", "slope_angle"],
+        unit_type: UnitType::Slope,
+        conversion: Conversion::Custom {
+            to_base: |degrees| degrees.to_radians().tan(),
+            from_base: |ratio| ratio.atan().to_degrees(),
+        },
+        description: "Angle of the incline from the horizontal. Used for terrain, roofs and ski runs; 45° is a 100% grade.",
+    },
+    // Fuel economy units (to kilometers per liter)
+    UnitDefinition {
+        name: "kilometers_per_liter",
+        symbol: "km/L",
+        aliases: &["km/l", "kmpl", "km_per_liter"],
+        unit_type: UnitType::FuelEconomy,
+        conversion: Conversion::Linear(1.0),
+        description: "Kilometers driven on one liter of fuel. The usual economy figure in Japan, India and much of Latin America.",
+    },
+    UnitDefinition {
+        name: "liters_per_100_km",
+        symbol: "L/100km",
+        aliases: &[
+            "l/100km",
+            "l_per_100km",
+            "liters_per_100km",
+            "liters_per_100_kilometers",
+        ],
+        unit_type: UnitType::FuelEconomy,
+        conversion: Conversion::Custom {
+            to_base: |liters| 100.0 / liters,
+            from_base: |kilometers_per_liter| 100.0 / kilometers_per_liter,
+        },
+        description: "Liters of fuel used every 100 km, the consumption figure on European, Australian and Chinese cars. Lower is better, so it runs opposite to miles per gallon.",
+    },
+    UnitDefinition {
+        name: "miles_per_gallon",
+        symbol: "mpg",
+        aliases: &["mpg", "us_mpg", "us_miles_per_gallon"],
+        unit_type: UnitType::FuelEconomy,
+        conversion: Conversion::Linear(MILE / 1000.0 / US_GALLON),
+        description: "Statute miles per US gallon, the economy rating on US window stickers.",
+    },
+    UnitDefinition {
+        name: "imperial_miles_per_gallon",
+        symbol: "mpg (imp)",
+        aliases: &["imperial_mpg", "uk_mpg", "mpg_imp"],
+        unit_type: UnitType::FuelEconomy,
+        conversion: Conversion::Linear(MILE / 1000.0 / IMPERIAL_GALLON),
+        description: "Statute miles per imperial gallon, as quoted in the UK. About 20% higher than the US figure for the same car because the imperial gallon is larger.",
+    },
+    UnitDefinition {
+        name: "nautical_miles_per_gallon",
+        symbol: "nmpg",
+        aliases: &["nmpg", "nm_per_gallon"],
+        unit_type: UnitType::FuelEconomy,
+        conversion: Conversion::Linear(NAUTICAL_MILE / 1000.0 / US_GALLON),
+        description: "Nautical miles per US gallon. Used by boaters to compare cruising speeds and plan range.",
+    },
+    UnitDefinition {
+        name: "liters_per_nautical_mile",
+        symbol: "L/nmi",
+        aliases: &["l/nmi", "l_per_nmi", "liters_per_nm"],
+        unit_type: UnitType::FuelEconomy,
+        conversion: Conversion::Custom {
+            to_base: |liters| NAUTICAL_MILE / 1000.0 / liters,
+            from_base: |kilometers_per_liter| NAUTICAL_MILE / 1000.0 / kilometers_per_liter,
+        },
+        description: "Liters of fuel burned per nautical mile, the consumption figure used in marine fuel planning and passage making. Lower is better.",
+    },
+    // Viscosity units (to centistokes)
+    UnitDefinition {
+        name: "centistokes",
+        symbol: "cSt",
+        aliases: &["cst", "centistoke", "mm²/s", "mm2/s"],
+        unit_type: UnitType::Viscosity,
+        conversion: Conversion::Linear(1.0),
+        description: "One hundredth of a stokes, equal to one square millimeter per second. The unit of ISO viscosity grades for lubricants; water at 20 °C is about 1 cSt.",
+    },
+    UnitDefinition {
+        name: "stokes",
+        symbol: "St",
+        aliases: &["stoke"],
+        unit_type: UnitType::Viscosity,
+        conversion: Conversion::Linear(100.0),
+        description: "The CGS unit of kinematic viscosity, one square centimeter per second, named after George Gabriel Stokes.",
+    },
+    UnitDefinition {
+        name: "saybolt_universal_seconds",
+        symbol: "SUS",
+        aliases: &["sus", "ssu", "saybolt_seconds"],
+        unit_type: UnitType::Viscosity,
+        conversion: Conversion::Custom {
+            to_base: sus_to_centistokes,
+            from_base: centistokes_to_sus,
+        },
+        description: "Seconds for 60 mL of oil to drain through a Saybolt Universal viscometer at 100 °F. Still quoted on older lubricant and hydraulic fluid specifications; converted with the ASTM D2161 correlation, which covers 32 SUS (1.81 cSt) and up.",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localized_names_are_accepted_as_input() {
+        for (locale, unit, singular, plural) in LOCALIZED_NAMES {
+            for word in [singular, plural] {
+                assert_eq!(
+                    localize(word, Some(*locale)),
+                    *unit,
+                    "{} ({})",
+                    word,
+                    locale
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn explicit_us_spellings_keep_their_meaning_under_other_systems() {
+        let imperial = Some(UnitSystem::Imperial);
+        assert_eq!(
+            resolve_all("mpg", imperial)[0].name,
+            "imperial_miles_per_gallon"
+        );
+        assert_eq!(resolve_all("us_mpg", imperial)[0].name, "miles_per_gallon");
+        assert_eq!(resolve_all("US_MPG", imperial)[0].name, "miles_per_gallon");
+    }
+
+    #[test]
+    fn every_system_dependent_unit_has_a_us_spelling() {
+        for system in [UnitSystem::Metric, UnitSystem::Imperial] {
+            for unit in system_dependent_units(system) {
+                let us_spelling = format!("us_{}", unit);
+                assert_eq!(resolve_all(&us_spelling, Some(system))[0].name, unit);
+                assert_ne!(resolve_all(unit, Some(system))[0].name, unit);
+            }
+        }
+        assert_eq!(
+            resolve_all("US gallons", Some(UnitSystem::Imperial))[0].name,
+            "gallons"
+        );
+        assert_eq!(
+            resolve_all("us_cup", Some(UnitSystem::Metric))[0].name,
+            "cups"
+        );
+    }
+
+    #[test]
+    fn ambiguity_errors_suggest_a_unit_system_only_when_none_is_set() {
+        let unset = disambiguate("cwt", None).unwrap();
+        assert!(unset.ends_with("or set a default unit system"), "{}", unset);
+        let metric = disambiguate("cwt", Some(UnitSystem::Metric)).unwrap();
+        assert!(!metric.contains("unit system"), "{}", metric);
+        assert!(disambiguate("cwt", Some(UnitSystem::Us)).is_none());
+        let points = disambiguate("pt", None).unwrap();
+        assert!(!points.contains("unit system"), "{}", points);
+    }
+
+    #[test]
+    fn saybolt_seconds_round_trip_from_the_floor_up() {
+        let floor = centistokes_to_sus(SAYBOLT_MIN_CENTISTOKES);
+        assert!((floor - 32.0).abs() < 0.5, "{}", floor);
+        assert!((sus_to_centistokes(floor) - SAYBOLT_MIN_CENTISTOKES).abs() < 1e-9);
+
+        for centistokes in [2.0, 20.65, 100.0, 10_000.0, 1e6] {
+            let sus = centistokes_to_sus(centistokes);
+            let round_trip = sus_to_centistokes(sus);
+            assert!(
+                (round_trip - centistokes).abs() <= centistokes * 1e-9,
+                "{} cSt -> {} SUS -> {} cSt",
+                centistokes,
+                sus,
+                round_trip
+            );
+        }
+        // ASTM D2161 tabulates 4 cSt as 39.1 SUS.
+        assert!((centistokes_to_sus(4.0) - 39.1).abs() < 0.2);
+    }
+
+    #[test]
+    fn saybolt_seconds_below_the_floor_have_no_equivalent() {
+        let floor = centistokes_to_sus(SAYBOLT_MIN_CENTISTOKES);
+        assert!(sus_to_centistokes(floor - 0.01).is_nan());
+        assert!(centistokes_to_sus(1.0).is_nan());
+        assert!(centistokes_to_sus(f64::NAN).is_nan());
+    }
+
+    fn humanized(unit: &str, value: f64, system: Option<UnitSystem>) -> (&'static str, f64) {
+        let (def, value) = humanize(
+            find(unit).unwrap(),
+            value,
+            system,
+            &ConversionContext::default(),
+        );
+        (def.name, value)
+    }
+
+    #[test]
+    fn humanize_picks_the_largest_unit_at_or_above_one() {
+        assert_eq!(humanized("meters", 2500.0, None), ("kilometers", 2.5));
+        assert_eq!(humanized("meters", -2500.0, None), ("kilometers", -2.5));
+        assert_eq!(humanized("meters", -0.25, None).0, "centimeters");
+        assert_eq!(humanized("meters", 0.005, None).0, "millimeters");
+        assert_eq!(
+            humanized("kilometers", 0.75, Some(UnitSystem::Us)).0,
+            "feet"
+        );
+    }
+
+    #[test]
+    fn humanize_falls_back_to_the_smallest_unit_below_one() {
+        let (name, value) = humanized("meters", 0.0004, None);
+        assert_eq!(name, "millimeters");
+        assert!((value - 0.4).abs() < 1e-12);
+        assert_eq!(humanized("meters", 0.0, None), ("millimeters", 0.0));
+        assert_eq!(humanized("meters", -0.0004, None).0, "millimeters");
+    }
+
+    #[test]
+    fn mixed_format_carries_rounded_remainders() {
+        let feet = find("feet").unwrap();
+        assert_eq!(format_mixed(5.5, feet).as_deref(), Some("5 ft 6 in"));
+        assert_eq!(format_mixed(5.99, feet).as_deref(), Some("6 ft 0 in"));
+        assert_eq!(format_mixed(-5.5, feet).as_deref(), Some("-5 ft 6 in"));
+        assert_eq!(format_mixed(-0.01, feet).as_deref(), Some("0 ft 0 in"));
+        let hands = find("hands").unwrap();
+        assert_eq!(format_mixed(15.5, hands).as_deref(), Some("15.2 hh"));
+        assert_eq!(format_mixed(1.0, find("meters").unwrap()), None);
+    }
+
+    #[test]
+    fn localized_names_agree_in_number() {
+        let celsius = find("celsius").unwrap();
+        assert_eq!(
+            localized_name(celsius, Locale::Es, 1.0),
+            Some("grado Celsius")
+        );
+        let feet = find("feet").unwrap();
+        assert_eq!(localized_name(feet, Locale::De, 2.0), Some("Fuß"));
+        let meters = find("meters").unwrap();
+        assert_eq!(localized_name(meters, Locale::It, 2.5), Some("metri"));
+    }
+}
@@ -0,0 +1,607 @@
+//! Dimensional analysis for composite unit expressions such as `g/cm^3` or `kg*m/s^2`.
+//!
+//! An expression is a product of registry units raised to integer powers. The time,
+//! energy and other derived SI units below stand in where the registry has none, as time
+//! and energy units are only registered with the scientific unit set. Each one reduces to a factor against SI base units and a
+//! [`Dimension`], so any two expressions with the same dimension can be converted.
+//!
+//! Rates may also be worded, as in `miles per hour` or `liters per 100 kilometers`, and
+//! may count something that is not a unit at all, as in `dollars per gallon`; such
+//! rates convert as long as both sides count the same thing.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt,
+    sync::{LazyLock, PoisonError, RwLock},
+};
+
+use anyhow::{Result, anyhow};
+
+use super::{Conversion, UnitSystem, UnitType};
+
+const BASE_DIMENSIONS: [&str; 4] = ["length", "mass", "time", "information"];
+
+/// Exponents of length, mass, time and information, in that order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Dimension([i8; 4]);
+
+impl Dimension {
+    pub const NONE: Dimension = Dimension([0, 0, 0, 0]);
+
+    const fn new(length: i8, mass: i8, time: i8, information: i8) -> Self {
+        Self([length, mass, time, information])
+    }
+
+    /// `None` when an exponent leaves the `i8` range.
+    fn times(self, other: Dimension) -> Option<Self> {
+        let mut exponents = self.0;
+        for (exponent, other) in exponents.iter_mut().zip(other.0) {
+            *exponent = exponent.checked_add(other)?;
+        }
+        Some(Self(exponents))
+    }
+
+    /// `None` when an exponent leaves the `i8` range.
+    fn powi(self, exponent: i8) -> Option<Self> {
+        let mut exponents = self.0;
+        for value in &mut exponents {
+            *value = value.checked_mul(exponent)?;
+        }
+        Some(Self(exponents))
+    }
+
+    /// Name of the physical quantity with this dimension, when it has a common one.
+    pub fn quantity(self) -> Option<&'static str> {
+        QUANTITIES
+            .iter()
+            .find(|(dimension, _)| *dimension == self)
+            .map(|(_, name)| *name)
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self == Dimension::NONE {
+            return write!(f, "dimensionless");
+        }
+
+        let terms: Vec<String> = BASE_DIMENSIONS
+            .iter()
+            .zip(self.0)
+            .filter(|(_, exponent)| *exponent != 0)
+            .map(|(name, exponent)| match exponent {
+                1 => name.to_string(),
+                exponent => format!("{}^{}", name, exponent),
+            })
+            .collect();
+        write!(f, "{}", terms.join("·"))
+    }
+}
+
+static QUANTITIES: &[(Dimension, &str)] = &[
+    (Dimension::new(1, 0, 0, 0), "distance"),
+    (Dimension::new(2, 0, 0, 0), "area"),
+    (Dimension::new(3, 0, 0, 0), "volume"),
+    (Dimension::new(0, 1, 0, 0), "weight"),
+    (Dimension::new(0, 0, 1, 0), "time"),
+    (Dimension::new(0, 0, 0, 1), "digital"),
+    (Dimension::new(0, 0, -1, 0), "frequency"),
+    (Dimension::new(1, 0, -1, 0), "speed"),
+    (Dimension::new(1, 0, -2, 0), "acceleration"),
+    (Dimension::new(-3, 1, 0, 0), "density"),
+    (Dimension::new(3, 0, -1, 0), "flow_rate"),
+    (Dimension::new(0, 1, -1, 0), "mass_flow_rate"),
+    (Dimension::new(1, 1, -2, 0), "force"),
+    (Dimension::new(-1, 1, -2, 0), "pressure"),
+    (Dimension::new(2, 1, -2, 0), "energy"),
+    (Dimension::new(2, 1, -3, 0), "power"),
+    (Dimension::new(0, 0, -1, 1), "data_rate"),
+    (Dimension::new(2, 0, -1, 0), "viscosity"),
+];
+
+/// Dimension of each registry category and the SI value of one of its base unit.
+const fn category_dimension(unit_type: UnitType) -> Option<(Dimension, f64)> {
+    match unit_type {
+        UnitType::Distance => Some((Dimension::new(1, 0, 0, 0), 1.0)),
+        UnitType::Volume => Some((Dimension::new(3, 0, 0, 0), 0.001)),
+        UnitType::Weight => Some((Dimension::new(0, 1, 0, 0), 1.0)),
+        UnitType::Digital => Some((Dimension::new(0, 0, 0, 1), 1.0)),
+        UnitType::Pressure => Some((Dimension::new(-1, 1, -2, 0), 1.0)),
+        UnitType::Speed => Some((Dimension::new(1, 0, -1, 0), 1.0)),
+        UnitType::DataRate => Some((Dimension::new(0, 0, -1, 1), 0.125)),
+        UnitType::Viscosity => Some((Dimension::new(2, 0, -1, 0), 1e-6)),
+        UnitType::Time => Some((Dimension::new(0, 0, 1, 0), 1.0)),
+        UnitType::Energy => Some((Dimension::new(2, 1, -2, 0), 1.0)),
+        UnitType::Temperature
+        | UnitType::Typography
+        | UnitType::Level
+        | UnitType::Slope
+        | UnitType::FuelEconomy => None,
+    }
+}
+
+/// Building blocks that have no category in the default registry. Registry units are
+/// looked up first, so the scientific set's time and energy units take over when enabled.
+static EXTRA_UNITS: &[(&[&str], f64, Dimension)] = &[
+    (
+        &["s", "sec", "second", "seconds"],
+        1.0,
+        Dimension::new(0, 0, 1, 0),
+    ),
+    (
+        &["min", "minute", "minutes"],
+        60.0,
+        Dimension::new(0, 0, 1, 0),
+    ),
+    (
+        &["h", "hr", "hour", "hours"],
+        3_600.0,
+        Dimension::new(0, 0, 1, 0),
+    ),
+    (&["d", "day", "days"], 86_400.0, Dimension::new(0, 0, 1, 0)),
+    (&["hz", "hertz"], 1.0, Dimension::new(0, 0, -1, 0)),
+    (
+        &["n", "newton", "newtons"],
+        1.0,
+        Dimension::new(1, 1, -2, 0),
+    ),
+    (&["j", "joule", "joules"], 1.0, Dimension::new(2, 1, -2, 0)),
+    (&["w", "watt", "watts"], 1.0, Dimension::new(2, 1, -3, 0)),
+];
+
+/// A unit expression reduced to SI base units: one of it equals `factor` of `dimension`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompoundUnit {
+    pub factor: f64,
+    pub dimension: Dimension,
+}
+
+impl CompoundUnit {
+    /// A plain number, such as the 100 in `L/(100*km)`.
+    const fn count(count: f64) -> Self {
+        Self {
+            factor: count,
+            dimension: Dimension::NONE,
+        }
+    }
+
+    fn times(self, other: CompoundUnit) -> Option<Self> {
+        Some(Self {
+            factor: self.factor * other.factor,
+            dimension: self.dimension.times(other.dimension)?,
+        })
+    }
+
+    fn powi(self, exponent: i8) -> Option<Self> {
+        Some(Self {
+            factor: self.factor.powi(exponent.into()),
+            dimension: self.dimension.powi(exponent)?,
+        })
+    }
+
+    fn atom(name: &str, system: Option<UnitSystem>) -> Result<Self> {
+        if let Ok(count) = name.parse::<f64>() {
+            return Ok(Self::count(count));
+        }
+
+        if let Some(error) = super::disambiguate(name, system) {
+            return Err(anyhow!("{}", error));
+        }
+        let matches = super::resolve_all(name, system);
+        if let Some((factor, dimension)) = matches.iter().find_map(|def| {
+            match (def.conversion, category_dimension(def.unit_type)) {
+                (Conversion::Linear(factor), Some((dimension, scale))) => {
+                    Some((factor * scale, dimension))
+                }
+                _ => None,
+            }
+        }) {
+            return Ok(Self { factor, dimension });
+        }
+
+        let name_lower = name.to_lowercase();
+        if let Some((_, factor, dimension)) = EXTRA_UNITS
+            .iter()
+            .find(|(names, _, _)| names.contains(&name_lower.as_str()))
+        {
+            return Ok(Self {
+                factor: *factor,
+                dimension: *dimension,
+            });
+        }
+
+        match matches.first() {
+            Some(def) => Err(anyhow!(
+                "{} units such as {} cannot be used in compound unit expressions",
+                def.unit_type.label(),
+                name
+            )),
+            None => Err(anyhow!("Unsupported unit: {}", name)),
+        }
+    }
+}
+
+/// Upper bound on cached expressions; the cache starts over once it is reached so that
+/// callers sending endless distinct expressions cannot grow it without limit.
+const CACHE_CAPACITY: usize = 1_024;
+
+type CacheKey = (String, Option<UnitSystem>);
+
+/// Expressions already reduced to a factor and dimension, shared by concurrent requests.
+static CACHE: LazyLock<RwLock<HashMap<CacheKey, CompoundUnit>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Parses an expression such as `kg*m/s^2`, `g/cm³` or `kg/(m·s^2)`.
+///
+/// Factors are joined with `*`, `·` or `/` and may be raised to integer powers with `^`,
+/// `²` or `³`; parentheses group, and whole numbers are dimensionless factors as in
+/// `1/s` or `L/(100*km)`. Worded rates are read through [`normalize`]. Successful parses
+/// are cached, so repeated expressions skip the parser entirely.
+pub fn parse(expression: &str, system: Option<UnitSystem>) -> Result<CompoundUnit> {
+    let expression = normalize(expression);
+    let expression = expression.as_ref();
+    let key = (expression.to_string(), system);
+    if let Some(unit) = CACHE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&key)
+    {
+        return Ok(*unit);
+    }
+
+    let unit = parse_uncached(expression, system)?;
+    let mut cache = CACHE.write().unwrap_or_else(PoisonError::into_inner);
+    if cache.len() >= CACHE_CAPACITY {
+        cache.clear();
+    }
+    cache.insert(key, unit);
+    Ok(unit)
+}
+
+/// Forgets cached expressions, whose factors may refer to units that were just replaced.
+pub(super) fn clear_cache() {
+    CACHE
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clear();
+}
+
+fn parse_uncached(expression: &str, system: Option<UnitSystem>) -> Result<CompoundUnit> {
+    let mut parser = Parser {
+        expression,
+        chars: expression.char_indices().peekable(),
+        system,
+    };
+
+    let unit = parser.product()?;
+    match parser.peek() {
+        None => Ok(unit),
+        Some(unexpected) => Err(parser.error(&format!("unexpected '{}'", unexpected))),
+    }
+}
+
+/// Rewrites a rate worded like `miles per hour` or `liters per 100 kilometers` into the
+/// symbolic form the parser reads (`miles/hour`, `liters/(100*kilometers)`), joining
+/// multi-word names with underscores as in `nautical_miles`. Expressions that already
+/// use operators are returned unchanged.
+pub fn normalize(expression: &str) -> Cow<'_, str> {
+    let words: Vec<&str> = expression.split_whitespace().collect();
+    if words.len() < 2 || !words.iter().all(|word| word.chars().all(is_name_char)) {
+        return Cow::Borrowed(expression);
+    }
+
+    let mut terms = Vec::new();
+    for group in words.split(|word| word.eq_ignore_ascii_case("per")) {
+        if group.is_empty() {
+            return Cow::Borrowed(expression);
+        }
+        let (counts, names): (Vec<&str>, Vec<&str>) =
+            group.iter().partition(|word| word.parse::<f64>().is_ok());
+        let mut factors: Vec<String> = counts.into_iter().map(str::to_string).collect();
+        if !names.is_empty() {
+            factors.push(names.join("_"));
+        }
+        terms.push(match factors.as_slice() {
+            [factor] => factor.clone(),
+            factors => format!("({})", factors.join("*")),
+        });
+    }
+    Cow::Owned(terms.join("/"))
+}
+
+/// Splits a rate such as `dollars/gallon` whose leading term is not a unit (a currency,
+/// or anything else being counted) into that term and the expression it is counted per.
+fn counted(expression: &str, system: Option<UnitSystem>) -> Option<(&str, &str)> {
+    let (item, per) = expression.split_once('/')?;
+    let item = item.trim();
+    let is_item = !item.is_empty()
+        && item.chars().all(is_name_char)
+        && item.parse::<f64>().is_err()
+        && CompoundUnit::atom(item, system).is_err()
+        && super::resolve_all(item, system).is_empty();
+    is_item.then_some((item, per))
+}
+
+/// Converts `value` between two expressions, returning the result and the shared dimension.
+pub fn convert(
+    value: f64,
+    from: &str,
+    to: &str,
+    system: Option<UnitSystem>,
+) -> Result<(f64, Dimension)> {
+    let (from_rate, to_rate) = (normalize(from), normalize(to));
+    if let (Some((from_item, from_per)), Some((to_item, to_per))) =
+        (counted(&from_rate, system), counted(&to_rate, system))
+    {
+        if !from_item.eq_ignore_ascii_case(to_item) {
+            return Err(anyhow!(
+                "Cannot convert {} to {}: only the units a rate is measured per can change, not what it counts ({} and {}; currency exchange is not supported)",
+                from,
+                to,
+                from_item,
+                to_item
+            ));
+        }
+
+        let (from_per, to_per) = (parse(from_per, system)?, parse(to_per, system)?);
+        if from_per.dimension != to_per.dimension {
+            return Err(anyhow!(
+                "Incompatible units: {} is per {} but {} is per {}",
+                from,
+                from_per.dimension,
+                to,
+                to_per.dimension
+            ));
+        }
+        let dimension = from_per.dimension.powi(-1).ok_or_else(|| {
+            anyhow!(
+                "Invalid unit expression \"{}\": exponent out of range",
+                from
+            )
+        })?;
+        return Ok((value * to_per.factor / from_per.factor, dimension));
+    }
+
+    let from_unit = parse(from, system)?;
+    let to_unit = parse(to, system)?;
+
+    if from_unit.dimension != to_unit.dimension {
+        return Err(anyhow!(
+            "Incompatible units: {} is {} but {} is {}",
+            from,
+            from_unit.dimension,
+            to,
+            to_unit.dimension
+        ));
+    }
+
+    Ok((
+        value * from_unit.factor / to_unit.factor,
+        from_unit.dimension,
+    ))
+}
+
+struct Parser<'a> {
+    expression: &'a str,
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    system: Option<UnitSystem>,
+}
+
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn error(&self, problem: &str) -> anyhow::Error {
+        anyhow!(
+            "Invalid unit expression \"{}\": {}",
+            self.expression,
+            problem
+        )
+    }
+
+    /// Rejects `unit` when combining it pushed an exponent out of range.
+    fn checked(&self, unit: Option<CompoundUnit>) -> Result<CompoundUnit> {
+        unit.ok_or_else(|| self.error("exponent out of range"))
+    }
+
+    /// `power (('*' | '·' | '/') power)*`
+    fn product(&mut self) -> Result<CompoundUnit> {
+        let mut unit = self.power()?;
+        loop {
+            match self.peek() {
+                Some('*' | '·' | '⋅') => {
+                    self.chars.next();
+                    let factor = self.power()?;
+                    unit = self.checked(unit.times(factor))?;
+                }
+                Some('/') => {
+                    self.chars.next();
+                    let divisor = self.power()?;
+                    let divisor = self.checked(divisor.powi(-1))?;
+                    unit = self.checked(unit.times(divisor))?;
+                }
+                _ => return Ok(unit),
+            }
+        }
+    }
+
+    /// `primary ('^' integer | '²' | '³')?`
+    fn power(&mut self) -> Result<CompoundUnit> {
+        let unit = self.primary()?;
+        match self.peek() {
+            Some('²') => {
+                self.chars.next();
+                self.checked(unit.powi(2))
+            }
+            Some('³') => {
+                self.chars.next();
+                self.checked(unit.powi(3))
+            }
+            Some('^') => {
+                self.chars.next();
+                self.peek();
+                let start = self.chars.peek().map(|(index, _)| *index);
+                let mut end = start;
+                while let Some((index, c)) = self.chars.next_if(|(index, c)| {
+                    c.is_ascii_digit() || (*c == '-' && Some(*index) == start)
+                }) {
+                    end = Some(index + c.len_utf8());
+                }
+                let digits = match (start, end) {
+                    (Some(start), Some(end)) => &self.expression[start..end],
+                    _ => "",
+                };
+                let exponent = digits
+                    .parse::<i8>()
+                    .map_err(|_| self.error("expected an integer exponent after '^'"))?;
+                self.checked(unit.powi(exponent))
+            }
+            _ => Ok(unit),
+        }
+    }
+
+    /// `'(' product ')' | unit name`
+    fn primary(&mut self) -> Result<CompoundUnit> {
+        match self.peek() {
+            Some('(') => {
+                self.chars.next();
+                let unit = self.product()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.chars.next();
+                        Ok(unit)
+                    }
+                    _ => Err(self.error("missing ')'")),
+                }
+            }
+            Some(c) if is_name_char(c) => {
+                let mut name = String::new();
+                while let Some((_, c)) = self.chars.next_if(|(_, c)| is_name_char(*c)) {
+                    name.push(c);
+                }
+                CompoundUnit::atom(&name, self.system)
+            }
+            Some(unexpected) => {
+                Err(self.error(&format!("expected a unit, found '{}'", unexpected)))
+            }
+            None => Err(self.error("expected a unit")),
+        }
+    }
+}
+
+/// Unit names are letters, digits, `_` and `°`; superscript powers are operators.
+fn is_name_char(c: char) -> bool {
+    (c.is_alphanumeric() && !matches!(c, '²' | '³')) || c == '_' || c == '°'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() <= expected.abs() * 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn parses_products_quotients_and_powers() {
+        let speed = parse("km/h", None).unwrap();
+        assert_close(speed.factor, 1000.0 / 3600.0);
+        assert_eq!(speed.dimension.quantity(), Some("speed"));
+
+        let pressure = parse("kg/(m·s^2)", None).unwrap();
+        assert_close(pressure.factor, 1.0);
+        assert_eq!(pressure.dimension.quantity(), Some("pressure"));
+
+        let density = parse("g/cm³", None).unwrap();
+        assert_close(density.factor, 1000.0);
+        assert_eq!(density.dimension.quantity(), Some("density"));
+
+        let frequency = parse("1/s", None).unwrap();
+        assert_eq!(frequency.dimension.quantity(), Some("frequency"));
+    }
+
+    #[test]
+    fn numbers_are_dimensionless_factors() {
+        let consumption = parse("L/(100*km)", None).unwrap();
+        assert_close(consumption.factor, 0.001 / 100_000.0);
+        assert_eq!(consumption.dimension, Dimension::new(2, 0, 0, 0));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expression in ["kg/(m*s", "m^", "m^x", "m**s", "m/", "m)"] {
+            let error = parse(expression, None).unwrap_err().to_string();
+            assert!(
+                error.starts_with("Invalid unit expression"),
+                "{}: {}",
+                expression,
+                error
+            );
+        }
+        assert!(parse("m/furlongs_of_cheese", None).is_err());
+    }
+
+    #[test]
+    fn rejects_exponents_out_of_range() {
+        for expression in ["m^100*m^100", "(m^127)^2", "1/s^-128", "m^128", "(m^100)³"] {
+            let error = parse(expression, None).unwrap_err().to_string();
+            assert!(
+                error.starts_with("Invalid unit expression"),
+                "{}: {}",
+                expression,
+                error
+            );
+        }
+        assert_eq!(
+            parse("m^127", None).unwrap().dimension,
+            Dimension::new(127, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn time_and_energy_need_no_unit_set() {
+        let flow = parse("m^3/h", None).unwrap();
+        assert_close(flow.factor, 1.0 / 3600.0);
+        assert_eq!(flow.dimension.quantity(), Some("flow_rate"));
+
+        let power = parse("J/s", None).unwrap();
+        assert_eq!(power.dimension.quantity(), Some("power"));
+        assert!(!super::super::categories().contains(&UnitType::Time));
+    }
+
+    #[test]
+    fn normalizes_worded_rates() {
+        assert_eq!(normalize("miles per hour"), "miles/hour");
+        assert_eq!(
+            normalize("liters per 100 kilometers"),
+            "liters/(100*kilometers)"
+        );
+        assert_eq!(normalize("nautical miles per hour"), "nautical_miles/hour");
+        assert_eq!(normalize("km/h"), "km/h");
+    }
+
+    #[test]
+    fn converts_between_expressions_of_one_dimension() {
+        let (value, dimension) = convert(36.0, "km/h", "m/s", None).unwrap();
+        assert_close(value, 10.0);
+        assert_eq!(dimension.quantity(), Some("speed"));
+
+        assert!(convert(1.0, "km/h", "kg/s", None).is_err());
+    }
+
+    #[test]
+    fn converts_counted_rates_only_for_the_same_item() {
+        let (value, _) = convert(1500.0, "dollars per liter", "dollars/ml", None).unwrap();
+        assert_close(value, 1.5);
+
+        assert!(convert(1.0, "dollars/gallon", "euros/liter", None).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! Custom units loaded from a JSON units file at startup.
+//!
+//! ```json
+//! {
+//!   "units": [
+//!     { "name": "smoots", "symbol": "smoot", "category": "distance", "factor": 1.7018 },
+//!     {
+//!       "name": "probe_counts",
+//!       "aliases": ["counts"],
+//!       "category": "temperature",
+//!       "description": "Raw readings of the boiler probe.",
+//!       "to_base": "0.0125 * x - 40",
+//!       "from_base": "(x + 40) / 0.0125"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! `factor` is the value of one unit in the category's base unit. Nonlinear units give
+//! `to_base` and `from_base` as [`Script`] expressions over `x` instead.
+
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+
+use super::{Conversion, UnitDefinition, UnitType, script::Script, sets::UnitSet};
+
+#[derive(Deserialize)]
+struct UnitsFile {
+    units: Vec<CustomUnit>,
+}
+
+#[derive(Deserialize)]
+struct CustomUnit {
+    name: String,
+    symbol: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    category: String,
+    description: Option<String>,
+    factor: Option<f64>,
+    to_base: Option<String>,
+    from_base: Option<String>,
+}
+
+impl CustomUnit {
+    fn into_definition(self) -> Result<UnitDefinition> {
+        let unit_type: UnitType = self.category.parse()?;
+        let conversion = match (self.factor, &self.to_base, &self.from_base) {
+            (Some(factor), None, None) if factor.is_finite() && factor != 0.0 => {
+                Conversion::Linear(factor)
+            }
+            (Some(factor), None, None) => {
+                return Err(anyhow!("Invalid factor: {} (must be non-zero)", factor));
+            }
+            (None, Some(to_base), Some(from_base)) => Conversion::Scripted {
+                to_base: Box::leak(Box::new(Script::compile(to_base)?)),
+                from_base: Box::leak(Box::new(Script::compile(from_base)?)),
+            },
+            _ => {
+                return Err(anyhow!(
+                    "Give either factor, or both to_base and from_base scripts"
+                ));
+            }
+        };
+
+        Ok(UnitDefinition {
+            name: leak(self.name.to_lowercase()),
+            symbol: leak(self.symbol.unwrap_or_else(|| self.name.clone())),
+            aliases: Box::leak(
+                self.aliases
+                    .into_iter()
+                    .map(|alias| leak(alias.to_lowercase()))
+                    .collect::<Vec<_>>()
+                    .into_boxed_slice(),
+            ),
+            unit_type,
+            conversion,
+            description: leak(
+                self.description
+                    .unwrap_or_else(|| "Custom unit defined in the units file.".to_string()),
+            ),
+        })
+    }
+}
+
+/// Custom units live as long as the server, like the built-in table.
+fn leak(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+/// Reads and validates the units file at `path`. Names and symbols already taken by a
+/// built-in unit, a unit in any optional set or an earlier entry are rejected so the file
+/// cannot silently change conversions.
+pub fn load(path: &Path) -> Result<Vec<UnitDefinition>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read units file {}", path.display()))?;
+    let file: UnitsFile = serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid units file {}", path.display()))?;
+
+    definitions(file.units).with_context(|| format!("Invalid units file {}", path.display()))
+}
+
+fn definitions(units: Vec<CustomUnit>) -> Result<Vec<UnitDefinition>> {
+    let mut definitions: Vec<UnitDefinition> = Vec::new();
+    for unit in units {
+        let name = unit.name.clone();
+        let definition = unit
+            .into_definition()
+            .with_context(|| format!("Invalid unit {}", name))?;
+
+        // The symbol counts too, as symbols are matched exactly before names.
+        let taken = [definition.name, definition.symbol]
+            .into_iter()
+            .chain(definition.aliases.iter().copied())
+            .find(|term| {
+                super::UNITS
+                    .iter()
+                    .chain(UnitSet::ALL.iter().flat_map(|set| set.units()))
+                    .chain(&definitions)
+                    .any(|other| other.symbol == *term || other.matches(&term.to_lowercase()))
+            });
+        if let Some(term) = taken {
+            return Err(anyhow!(
+                "Unit {} reuses the name or symbol \"{}\", which is already defined",
+                name,
+                term
+            ));
+        }
+        definitions.push(definition);
+    }
+
+    Ok(definitions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_unit(name: &str, symbol: Option<&str>, category: &str) -> CustomUnit {
+        CustomUnit {
+            name: name.to_string(),
+            symbol: symbol.map(str::to_string),
+            aliases: Vec::new(),
+            category: category.to_string(),
+            description: None,
+            factor: Some(1.7018),
+            to_base: None,
+            from_base: None,
+        }
+    }
+
+    fn rejection(units: Vec<CustomUnit>) -> String {
+        match definitions(units) {
+            Ok(_) => panic!("units were accepted"),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn accepts_new_names_and_symbols() {
+        let units = definitions(vec![custom_unit("smoots", Some("smoot"), "distance")]).unwrap();
+        assert_eq!(units[0].name, "smoots");
+    }
+
+    #[test]
+    fn rejects_symbols_of_existing_units() {
+        for (symbol, category) in [("m", "weight"), ("kg", "distance"), ("MB/s", "distance")] {
+            let error = rejection(vec![custom_unit("smoots", Some(symbol), category)]);
+            assert!(error.contains("reuses the name or symbol"), "{}", error);
+        }
+
+        let error = rejection(vec![
+            custom_unit("smoots", Some("smoot"), "distance"),
+            custom_unit("smoot_halves", Some("smoot"), "distance"),
+        ]);
+        assert!(error.contains("\"smoot\""), "{}", error);
+    }
+}
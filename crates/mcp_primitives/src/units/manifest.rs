@@ -0,0 +1,180 @@
+//! Snapshots of the unit registry, kept so a later registry can be compared against them.
+//!
+//! A snapshot records what each unit converts as rather than how it is implemented, so two
+//! registries built by different server versions still compare meaningfully.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::{ConversionContext, UnitDefinition};
+
+/// One unit as seen by callers: its category, symbol and conversion to the base unit.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UnitRecord {
+    pub name: String,
+    pub category: String,
+    pub symbol: String,
+    pub conversion: String,
+    /// One unit expressed in the category's base unit, when that is a finite number.
+    pub base_value: Option<f64>,
+}
+
+impl UnitRecord {
+    fn new(def: &UnitDefinition) -> Self {
+        let base_value = def.conversion.to_base(1.0, &ConversionContext::default());
+        Self {
+            name: def.name.to_string(),
+            category: def.unit_type.to_string(),
+            symbol: def.symbol.to_string(),
+            conversion: def.conversion_summary(),
+            base_value: base_value.is_finite().then_some(base_value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Manifest {
+    /// Server version that built the registry.
+    pub version: String,
+    pub units: Vec<UnitRecord>,
+}
+
+impl Manifest {
+    /// The registry as it stands now.
+    pub fn current(version: &str) -> Self {
+        Self {
+            version: version.to_string(),
+            units: super::catalog().map(UnitRecord::new).collect(),
+        }
+    }
+
+    /// Reads a manifest saved by [`Manifest::save`], or `None` if `path` does not exist yet.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read registry snapshot {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .with_context(|| format!("Invalid registry snapshot {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write registry snapshot {}", path.display()))
+    }
+
+    /// Units added, removed and changed going from `self` to `after`.
+    pub fn diff(&self, after: &Manifest) -> Value {
+        let (added, removed, changed) = self.changes(after);
+        let changed: Vec<_> = changed
+            .into_iter()
+            .map(|(old, new)| {
+                json!({ "name": old.name, "category": old.category, "before": old, "after": new })
+            })
+            .collect();
+
+        json!({
+            "from_version": self.version,
+            "to_version": after.version,
+            "unchanged": added.is_empty() && removed.is_empty() && changed.is_empty(),
+            "added": added,
+            "removed": removed,
+            "changed": changed
+        })
+    }
+
+    /// Records added, removed and changed (as before and after pairs) going from `self` to
+    /// `after`. Units are matched by category and name, as some names, such as millimeters,
+    /// are registered in more than one category.
+    fn changes<'a>(
+        &'a self,
+        after: &'a Manifest,
+    ) -> (
+        Vec<&'a UnitRecord>,
+        Vec<&'a UnitRecord>,
+        Vec<(&'a UnitRecord, &'a UnitRecord)>,
+    ) {
+        let key = |r: &'a UnitRecord| ((r.category.as_str(), r.name.as_str()), r);
+        let before: BTreeMap<_, _> = self.units.iter().map(key).collect();
+        let after_units: BTreeMap<_, _> = after.units.iter().map(key).collect();
+
+        let added = after_units
+            .iter()
+            .filter(|(key, _)| !before.contains_key(*key))
+            .map(|(_, record)| *record)
+            .collect();
+        let removed = before
+            .iter()
+            .filter(|(key, _)| !after_units.contains_key(*key))
+            .map(|(_, record)| *record)
+            .collect();
+        let changed = before
+            .iter()
+            .filter_map(|(key, old)| {
+                let new = after_units.get(key)?;
+                (old != new).then_some((*old, *new))
+            })
+            .collect();
+        (added, removed, changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, category: &str, base_value: f64) -> UnitRecord {
+        UnitRecord {
+            name: name.to_string(),
+            category: category.to_string(),
+            symbol: name.to_string(),
+            conversion: format!("{} base", base_value),
+            base_value: Some(base_value),
+        }
+    }
+
+    fn manifest(units: Vec<UnitRecord>) -> Manifest {
+        Manifest {
+            version: "0.1.0".to_string(),
+            units,
+        }
+    }
+
+    #[test]
+    fn same_named_units_in_different_categories_are_compared_separately() {
+        let before = manifest(vec![
+            record("millimeters", "distance", 0.001),
+            record("millimeters", "typography", 3.7795),
+        ]);
+        let after = manifest(vec![
+            record("millimeters", "distance", 0.0011),
+            record("millimeters", "typography", 3.7795),
+        ]);
+
+        let (added, removed, changed) = before.changes(&after);
+        assert!(added.is_empty());
+        assert!(removed.is_empty());
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].0.category, "distance");
+        assert_eq!(changed[0].1.base_value, Some(0.0011));
+    }
+
+    #[test]
+    fn removing_one_of_two_same_named_units_is_reported() {
+        let before = manifest(vec![
+            record("inches", "distance", 0.0254),
+            record("inches", "typography", 96.0),
+        ]);
+        let after = manifest(vec![record("inches", "typography", 96.0)]);
+
+        let (added, removed, changed) = before.changes(&after);
+        assert!(added.is_empty());
+        assert!(changed.is_empty());
+        assert_eq!(removed, [&before.units[0]]);
+    }
+}
@@ -0,0 +1,165 @@
+//! Real-world bounds per category, loaded from a JSON file at startup, that flag
+//! conversions whose values are unlikely to be right.
+//!
+//! ```json
+//! {
+//!   "weight": { "label": "human weight", "min": 0.5, "max": 650, "unit": "kilograms" },
+//!   "temperature": { "label": "air temperature", "min": -90, "max": 60, "unit": "celsius" }
+//! }
+//! ```
+//!
+//! Either bound may be left out. A converted value outside the bounds still converts, but
+//! the result carries a warning, which lets agents catch values such as a person weighing
+//! 4,000 kg after pounds and kilograms were mixed up upstream.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+
+use super::{ConversionContext, UnitDefinition, UnitType};
+
+#[derive(Deserialize)]
+struct BoundsEntry {
+    label: Option<String>,
+    min: Option<f64>,
+    max: Option<f64>,
+    unit: String,
+}
+
+struct CategoryBounds {
+    unit_type: UnitType,
+    label: String,
+    unit: &'static UnitDefinition,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+pub struct PlausibilityBounds {
+    categories: Vec<CategoryBounds>,
+}
+
+impl PlausibilityBounds {
+    /// Reads and validates the bounds file at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read plausibility file {}", path.display()))?;
+        let entries: BTreeMap<String, BoundsEntry> = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid plausibility file {}", path.display()))?;
+
+        Self::from_entries(entries).with_context(|| format!("Invalid bounds in {}", path.display()))
+    }
+
+    fn from_entries(entries: BTreeMap<String, BoundsEntry>) -> Result<Self> {
+        let categories = entries
+            .into_iter()
+            .map(|(category, entry)| {
+                let unit_type: UnitType = category.parse()?;
+                // Resolved like tool arguments, so case-sensitive symbols such as mW keep
+                // their meaning and names shared by several categories, such as mm, are
+                // read in this one.
+                let unit = super::resolve_all(&entry.unit, None)
+                    .into_iter()
+                    .find(|unit| unit.unit_type == unit_type)
+                    .ok_or_else(|| anyhow!("{} is not a {} unit", entry.unit, unit_type))?;
+                if let (Some(min), Some(max)) = (entry.min, entry.max)
+                    && min > max
+                {
+                    return Err(anyhow!("min {} is greater than max {}", min, max));
+                }
+                Ok(CategoryBounds {
+                    unit_type,
+                    label: entry.label.unwrap_or_else(|| unit_type.to_string()),
+                    unit,
+                    min: entry.min,
+                    max: entry.max,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { categories })
+    }
+
+    /// Why `base_value`, a quantity of `unit_type` in its base unit, is implausible, or
+    /// `None` when it is within bounds or the category has none.
+    pub fn check(&self, unit_type: UnitType, base_value: f64) -> Option<String> {
+        let bounds = self
+            .categories
+            .iter()
+            .find(|bounds| bounds.unit_type == unit_type)?;
+        // Compared in the bounds' own unit, as conversions such as L/100 km decrease
+        // while their base unit increases.
+        let value = bounds
+            .unit
+            .conversion
+            .from_base(base_value, &ConversionContext::default());
+        let shown = super::format_readable(value);
+        let name = bounds.unit.name.replace('_', " ");
+
+        if let Some(min) = bounds.min
+            && value < min
+        {
+            return Some(format!(
+                "{} {} is below the plausible minimum of {} {} for {}; check the source value and units",
+                shown, name, min, name, bounds.label
+            ));
+        }
+        if let Some(max) = bounds.max
+            && value > max
+        {
+            return Some(format!(
+                "{} {} is above the plausible maximum of {} {} for {}; check the source value and units",
+                shown, name, max, name, bounds.label
+            ));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(category: &str, unit: &str, min: f64, max: f64) -> PlausibilityBounds {
+        PlausibilityBounds::from_entries(BTreeMap::from([(
+            category.to_string(),
+            BoundsEntry {
+                label: None,
+                min: Some(min),
+                max: Some(max),
+                unit: unit.to_string(),
+            },
+        )]))
+        .unwrap()
+    }
+
+    fn base(unit: &str, value: f64) -> f64 {
+        super::super::find(unit)
+            .unwrap()
+            .conversion
+            .to_base(value, &ConversionContext::default())
+    }
+
+    #[test]
+    fn bounds_in_a_decreasing_unit_keep_their_order() {
+        let bounds = bounds("fuel_economy", "liters_per_100_km", 3.0, 30.0);
+        let check = |value| bounds.check(UnitType::FuelEconomy, base("liters_per_100_km", value));
+
+        assert_eq!(check(8.0), None);
+        assert!(check(2.0).unwrap().contains("below the plausible minimum"));
+        assert!(check(45.0).unwrap().contains("above the plausible maximum"));
+        assert_eq!(bounds.check(UnitType::FuelEconomy, base("mpg", 30.0)), None);
+    }
+
+    #[test]
+    fn bounds_units_resolve_within_their_category() {
+        let typography = bounds("typography", "mm", 1.0, 500.0);
+        assert_eq!(
+            typography.categories[0].unit.unit_type,
+            UnitType::Typography
+        );
+
+        let data_rate = bounds("data_rate", "MB/s", 0.0, 10_000.0);
+        assert_eq!(data_rate.categories[0].unit.name, "megabytes_per_second");
+    }
+}
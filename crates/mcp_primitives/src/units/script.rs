@@ -0,0 +1,82 @@
+//! Sandboxed Rhai expressions for custom conversions declared in the units file.
+//!
+//! A script is a single expression over `x` (e.g. `0.0125 * x - 40`), so it cannot loop,
+//! define functions or touch the host. Only arithmetic, comparison and the basic math
+//! functions (`sqrt`, `exp`, `ln`, `log`, `sin`, ...) are available, and evaluation is cut
+//! off after a fixed number of operations or [`SCRIPT_TIMEOUT`], whichever comes first.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use rhai::{
+    AST, Dynamic, Engine, Scope,
+    packages::{BasicMathPackage, LogicPackage, Package},
+};
+
+/// Wall-clock budget for one evaluation.
+pub const SCRIPT_TIMEOUT: Duration = Duration::from_millis(50);
+
+const MAX_OPERATIONS: u64 = 10_000;
+
+pub struct Script {
+    source: String,
+    ast: AST,
+}
+
+impl Script {
+    pub fn compile(source: &str) -> Result<Self> {
+        let ast = Self::engine(None)
+            .compile_expression(source)
+            .map_err(|error| anyhow!("Invalid script \"{}\": {}", source, error))?;
+        Ok(Self {
+            source: source.to_string(),
+            ast,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluates the expression with `x` bound to `value`.
+    pub fn eval(&self, value: f64) -> Result<f64> {
+        let mut scope = Scope::new();
+        scope.push("x", value);
+
+        let result: Dynamic = Self::engine(Some(Instant::now() + SCRIPT_TIMEOUT))
+            .eval_ast_with_scope(&mut scope, &self.ast)
+            .map_err(|error| anyhow!("Script \"{}\" failed: {}", self.source, error))?;
+        result
+            .as_float()
+            .or_else(|_| result.as_int().map(|value| value as f64))
+            .map_err(|type_name| {
+                anyhow!(
+                    "Script \"{}\" returned {} instead of a number",
+                    self.source,
+                    type_name
+                )
+            })
+    }
+
+    /// A fresh engine with no I/O and tight limits, stopping at `deadline` when given.
+    fn engine(deadline: Option<Instant>) -> Engine {
+        let mut engine = Engine::new_raw();
+        BasicMathPackage::new().register_into_engine(&mut engine);
+        LogicPackage::new().register_into_engine(&mut engine);
+        engine
+            .set_max_operations(MAX_OPERATIONS)
+            .set_max_expr_depths(32, 32)
+            .set_max_call_levels(8)
+            .set_max_string_size(256)
+            .set_max_array_size(256)
+            .set_max_map_size(256)
+            .on_print(|_| {})
+            .on_debug(|_, _, _| {});
+        if let Some(deadline) = deadline {
+            engine.on_progress(move |_| {
+                (Instant::now() > deadline).then(|| Dynamic::from("time limit exceeded"))
+            });
+        }
+        engine
+    }
+}
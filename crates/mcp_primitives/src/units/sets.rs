@@ -0,0 +1,388 @@
+//! Optional groups of trade-specific units, enabled or disabled as a whole.
+//!
+//! Sets in [`UnitSet::DEFAULT`] are registered at startup; deployments that want a
+//! leaner catalog, or a different selection, pass a comma-separated list such as
+//! `beverage,scientific,historical` (or `none`) through configuration.
+
+use std::{fmt, str::FromStr};
+
+use anyhow::{Result, anyhow};
+
+use super::{Conversion, IMPERIAL_GALLON, US_GALLON, UnitDefinition, UnitType};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnitSet {
+    /// Brewing casks and wine bottle sizes.
+    Beverage,
+    /// Particle physics and atomic-scale units, with the everyday time and energy units
+    /// they compare against, off unless configured.
+    Scientific,
+    /// Temperature scales of historical interest, off unless configured.
+    Historical,
+}
+
+impl UnitSet {
+    pub const ALL: [UnitSet; 3] = [UnitSet::Beverage, UnitSet::Scientific, UnitSet::Historical];
+
+    /// Sets enabled unless the configuration says otherwise.
+    pub const DEFAULT: [UnitSet; 1] = [UnitSet::Beverage];
+
+    pub const fn units(self) -> &'static [UnitDefinition] {
+        match self {
+            UnitSet::Beverage => BEVERAGE_UNITS,
+            UnitSet::Scientific => SCIENTIFIC_UNITS,
+            UnitSet::Historical => HISTORICAL_UNITS,
+        }
+    }
+}
+
+impl fmt::Display for UnitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitSet::Beverage => write!(f, "beverage"),
+            UnitSet::Scientific => write!(f, "scientific"),
+            UnitSet::Historical => write!(f, "historical"),
+        }
+    }
+}
+
+impl FromStr for UnitSet {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim().to_lowercase();
+        UnitSet::ALL
+            .into_iter()
+            .find(|set| set.to_string() == value)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unsupported unit set: {} (expected {})",
+                    value,
+                    UnitSet::ALL.map(|set| set.to_string()).join(", ")
+                )
+            })
+    }
+}
+
+/// A comma-separated selection of unit sets, where `none` or an empty list selects none.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UnitSets(pub Vec<UnitSet>);
+
+impl FromStr for UnitSets {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        if value.trim().eq_ignore_ascii_case("none") {
+            return Ok(Self::default());
+        }
+
+        let mut sets = Vec::new();
+        for set in value.split(',').filter(|set| !set.trim().is_empty()) {
+            let set: UnitSet = set.parse()?;
+            if !sets.contains(&set) {
+                sets.push(set);
+            }
+        }
+        Ok(Self(sets))
+    }
+}
+
+static BEVERAGE_UNITS: &[UnitDefinition] = &[
+    // Casks (to liters)
+    UnitDefinition {
+        name: "firkins",
+        symbol: "firkin",
+        aliases: &["firkin"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(9.0 * IMPERIAL_GALLON),
+        description: "A quarter of a UK beer barrel, nine imperial gallons (about 40.9 L). The usual cask for real ale served in British pubs.",
+    },
+    UnitDefinition {
+        name: "kilderkins",
+        symbol: "kilderkin",
+        aliases: &["kilderkin"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(18.0 * IMPERIAL_GALLON),
+        description: "Two firkins, eighteen imperial gallons (about 81.8 L). Common at beer festivals and for busier pub lines.",
+    },
+    UnitDefinition {
+        name: "uk_beer_barrels",
+        symbol: "UK bbl",
+        aliases: &["uk_beer_barrel", "beer_barrel_uk"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(36.0 * IMPERIAL_GALLON),
+        description: "Thirty-six imperial gallons (about 163.7 L). The reference volume for brewery output and duty in the UK.",
+    },
+    UnitDefinition {
+        name: "us_beer_barrels",
+        symbol: "US bbl",
+        aliases: &["us_beer_barrel", "beer_barrel", "beer_barrels"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(31.0 * US_GALLON),
+        description: "Thirty-one US gallons (about 117.3 L), the measure of US brewery production. A full-size keg is half a barrel; distinct from the 42-gallon oil barrel.",
+    },
+    UnitDefinition {
+        name: "hogsheads",
+        symbol: "hhd",
+        aliases: &["hogshead", "hhd", "beer_hogshead"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(54.0 * IMPERIAL_GALLON),
+        description: "A UK beer hogshead of one and a half barrels, fifty-four imperial gallons (about 245.5 L).",
+    },
+    UnitDefinition {
+        name: "wine_hogsheads",
+        symbol: "wine hhd",
+        aliases: &["wine_hogshead"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(63.0 * US_GALLON),
+        description: "The old English wine hogshead of sixty-three wine gallons, the gallon the US later adopted (about 238.5 L). Close to the 225 L Bordeaux barrique still used for ageing wine.",
+    },
+    // Bottles (to liters)
+    UnitDefinition {
+        name: "half_bottles",
+        symbol: "half bottle",
+        aliases: &["half_bottle", "demi"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(0.375),
+        description: "Half a standard wine bottle, 375 mL. Common for dessert wines and restaurant lists.",
+    },
+    UnitDefinition {
+        name: "wine_bottles",
+        symbol: "bottle",
+        aliases: &["wine_bottle", "bottle", "bottles"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(0.75),
+        description: "The standard 750 mL wine and spirits bottle, about five glasses of wine.",
+    },
+    UnitDefinition {
+        name: "magnums",
+        symbol: "magnum",
+        aliases: &["magnum"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(1.5),
+        description: "Two standard bottles, 1.5 L. Favoured for ageing fine wine and for celebrations.",
+    },
+    UnitDefinition {
+        name: "jeroboams",
+        symbol: "jeroboam",
+        aliases: &["jeroboam", "double_magnum", "double_magnums"],
+        unit_type: UnitType::Volume,
+        conversion: Conversion::Linear(3.0),
+        description: "Four standard bottles, 3 L, as named in Champagne; also called a double magnum. Bordeaux once used the name for a 4.5 L bottle.",
+    },
+];
+
+/// The electronvolt in joules, exact since the 2019 SI redefinition.
+const ELECTRONVOLT: f64 = 1.602_176_634e-19;
+
+/// The mass equivalent of one electronvolt, in kilograms (eV divided by c²).
+const ELECTRONVOLT_MASS: f64 = 1.782_661_921e-36;
+
+static SCIENTIFIC_UNITS: &[UnitDefinition] = &[
+    // Everyday time units (to seconds), the reference for Planck time
+    UnitDefinition {
+        name: "seconds",
+        symbol: "s",
+        aliases: &["sec", "second"],
+        unit_type: UnitType::Time,
+        conversion: Conversion::Linear(1.0),
+        description: "SI base unit of time, defined by the caesium-133 hyperfine transition frequency.",
+    },
+    UnitDefinition {
+        name: "minutes",
+        symbol: "min",
+        aliases: &["min", "minute"],
+        unit_type: UnitType::Time,
+        conversion: Conversion::Linear(60.0),
+        description: "Sixty seconds.",
+    },
+    UnitDefinition {
+        name: "hours",
+        symbol: "h",
+        aliases: &["h", "hr", "hour"],
+        unit_type: UnitType::Time,
+        conversion: Conversion::Linear(3_600.0),
+        description: "Sixty minutes, 3,600 seconds.",
+    },
+    UnitDefinition {
+        name: "days",
+        symbol: "d",
+        aliases: &["d", "day"],
+        unit_type: UnitType::Time,
+        conversion: Conversion::Linear(86_400.0),
+        description: "Twenty-four hours, 86,400 seconds, ignoring leap seconds and daylight saving changes.",
+    },
+    // Everyday energy units (to joules), the reference for the electronvolt units
+    UnitDefinition {
+        name: "joules",
+        symbol: "J",
+        aliases: &["j", "joule"],
+        unit_type: UnitType::Energy,
+        conversion: Conversion::Linear(1.0),
+        description: "SI unit of energy, the work done by one newton over one meter, named after James Prescott Joule.",
+    },
+    UnitDefinition {
+        name: "kilojoules",
+        symbol: "kJ",
+        aliases: &["kj", "kilojoule"],
+        unit_type: UnitType::Energy,
+        conversion: Conversion::Linear(1_000.0),
+        description: "One thousand joules. Shown next to calories on food labels in Europe and Australia.",
+    },
+    UnitDefinition {
+        name: "calories",
+        symbol: "cal",
+        aliases: &["cal", "calorie"],
+        unit_type: UnitType::Energy,
+        conversion: Conversion::Linear(4.184),
+        description: "The thermochemical calorie of exactly 4.184 J, roughly the energy to warm a gram of water by 1 °C. Used in chemistry.",
+    },
+    UnitDefinition {
+        name: "kilocalories",
+        symbol: "kcal",
+        aliases: &["kcal", "kilocalorie", "food_calories"],
+        unit_type: UnitType::Energy,
+        conversion: Conversion::Linear(4_184.0),
+        description: "One thousand calories, the \"Calorie\" on food labels and in diet tracking.",
+    },
+    UnitDefinition {
+        name: "kilowatt_hours",
+        symbol: "kWh",
+        aliases: &["kwh", "kilowatt_hour"],
+        unit_type: UnitType::Energy,
+        conversion: Conversion::Linear(3_600_000.0),
+        description: "One kilowatt sustained for an hour, 3.6 MJ. The unit of household electricity bills and battery capacity.",
+    },
+    // Energy (to joules)
+    UnitDefinition {
+        name: "electronvolts",
+        symbol: "eV",
+        aliases: &["ev", "electronvolt", "electron_volts"],
+        unit_type: UnitType::Energy,
+        conversion: Conversion::Linear(ELECTRONVOLT),
+        description: "The energy an electron gains across one volt, exactly 1.602176634×10⁻¹⁹ J. The everyday unit of atomic and solid-state physics.",
+    },
+    UnitDefinition {
+        name: "megaelectronvolts",
+        symbol: "MeV",
+        aliases: &["megaelectronvolt"],
+        unit_type: UnitType::Energy,
+        conversion: Conversion::Linear(ELECTRONVOLT * 1e6),
+        description: "One million electronvolts. The scale of nuclear reactions and gamma rays.",
+    },
+    UnitDefinition {
+        name: "gigaelectronvolts",
+        symbol: "GeV",
+        aliases: &["gigaelectronvolt"],
+        unit_type: UnitType::Energy,
+        conversion: Conversion::Linear(ELECTRONVOLT * 1e9),
+        description: "One billion electronvolts. The scale of particle accelerator collisions; a proton's rest energy is about 0.938 GeV.",
+    },
+    UnitDefinition {
+        name: "hartrees",
+        symbol: "Eh",
+        aliases: &["hartree"],
+        unit_type: UnitType::Energy,
+        conversion: Conversion::Linear(4.359_744_722_207_1e-18),
+        description: "The atomic unit of energy, about 27.2 eV, twice the ionization energy of hydrogen. Used in quantum chemistry.",
+    },
+    // Mass (to kilograms)
+    UnitDefinition {
+        name: "electronvolts_per_c2",
+        symbol: "eV/c²",
+        aliases: &["ev/c2", "ev/c²"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(ELECTRONVOLT_MASS),
+        description: "The mass equivalent of one electronvolt through E = mc². Particle physicists quote masses this way; the electron is about 511 keV/c².",
+    },
+    UnitDefinition {
+        name: "megaelectronvolts_per_c2",
+        symbol: "MeV/c²",
+        aliases: &["mev/c2", "mev/c²"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(ELECTRONVOLT_MASS * 1e6),
+        description: "One million eV/c². The electron weighs 0.511 MeV/c² and the proton 938.3 MeV/c².",
+    },
+    UnitDefinition {
+        name: "gigaelectronvolts_per_c2",
+        symbol: "GeV/c²",
+        aliases: &["gev/c2", "gev/c²"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(ELECTRONVOLT_MASS * 1e9),
+        description: "One billion eV/c². The Higgs boson weighs about 125 GeV/c².",
+    },
+    UnitDefinition {
+        name: "atomic_mass_units",
+        symbol: "u",
+        aliases: &["amu", "dalton", "daltons", "da"],
+        unit_type: UnitType::Weight,
+        conversion: Conversion::Linear(1.660_539_066_60e-27),
+        description: "One twelfth of the mass of a carbon-12 atom, also called the dalton (Da). Used for atomic, molecular and protein masses.",
+    },
+    // Length (to meters)
+    UnitDefinition {
+        name: "bohr_radii",
+        symbol: "a₀",
+        aliases: &["a0", "bohr", "bohr_radius"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(5.291_772_109_03e-11),
+        description: "The atomic unit of length, about 0.529 Å, the most probable electron distance from the nucleus in a hydrogen atom.",
+    },
+    UnitDefinition {
+        name: "planck_lengths",
+        symbol: "ℓP",
+        aliases: &["lp", "planck_length"],
+        unit_type: UnitType::Distance,
+        conversion: Conversion::Linear(1.616_255e-35),
+        description: "The Planck length, about 1.6×10⁻³⁵ m, built from the speed of light, the gravitational constant and the reduced Planck constant. The scale at which quantum gravity is expected to matter.",
+    },
+    // Time (to seconds)
+    UnitDefinition {
+        name: "planck_times",
+        symbol: "tP",
+        aliases: &["tp", "planck_time"],
+        unit_type: UnitType::Time,
+        conversion: Conversion::Linear(5.391_247e-44),
+        description: "The time light takes to cross one Planck length, about 5.4×10⁻⁴⁴ s.",
+    },
+];
+
+static HISTORICAL_UNITS: &[UnitDefinition] = &[
+    // Temperature (to celsius)
+    UnitDefinition {
+        name: "degrees_delisle",
+        symbol: "°De",
+        aliases: &["delisle"],
+        unit_type: UnitType::Temperature,
+        conversion: Conversion::Affine {
+            offset: 150.0,
+            numerator: -2.0,
+            denominator: 3.0,
+        },
+        description: "Devised by Joseph-Nicolas Delisle (1732) and used in Russia for about a century. It runs backwards: water boils at 0 and freezes at 150.",
+    },
+    UnitDefinition {
+        name: "degrees_newton",
+        symbol: "°N",
+        aliases: &["newton_scale"],
+        unit_type: UnitType::Temperature,
+        conversion: Conversion::Affine {
+            offset: 0.0,
+            numerator: 100.0,
+            denominator: 33.0,
+        },
+        description: "Isaac Newton's scale of about 1701, with water freezing at 0 and boiling at 33.",
+    },
+    UnitDefinition {
+        name: "degrees_romer",
+        symbol: "°Rø",
+        aliases: &["romer", "rømer", "degrees_rømer"],
+        unit_type: UnitType::Temperature,
+        conversion: Conversion::Affine {
+            offset: 7.5,
+            numerator: 40.0,
+            denominator: 21.0,
+        },
+        description: "Ole Rømer's scale of 1701, a forerunner of Fahrenheit's, with water freezing at 7.5 and boiling at 60.",
+    },
+];
@@ -1,27 +1,126 @@
-use std::{env, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    env,
+    fs::{File, OpenOptions},
+    io::Write as _,
+    path::PathBuf,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
-use anyhow::Result;
-use context_server::{ContextServer, ContextServerRpcRequest, ContextServerRpcResponse};
+use anyhow::{Context, Result, anyhow};
+use context_server::{ContextServer, ContextServerRpcRequest};
 use context_server_utils::{
     prompt_registry::PromptRegistry, resource_registry::ResourceRegistry,
     tool_registry::ToolRegistry,
 };
+use serde_json::{Value, json};
 use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader};
-use unit_conversion_mcp_primitives::tools::UnitConversion;
+#[cfg(unix)]
+use tokio::signal::unix::SignalKind;
+use unit_conversion_mcp_primitives::{
+    diagnostics,
+    plugins::Plugins,
+    prompts::ConversionQuiz,
+    resources::CategoryDocumentation,
+    telemetry::Telemetry,
+    tools::{
+        self, AnnotatedTool, BatchLimits, ConversionHistory, ConvertToAll, History, ListUnits,
+        RackCapacity, RegistryDiff, ToolAnnotations, UnitConversion,
+    },
+    units::{self, manifest::Manifest, plausibility::PlausibilityBounds, sets::UnitSets},
+};
 
 struct ContextServerState {
     rpc: ContextServer,
+    annotations: HashMap<String, ToolAnnotations>,
+    telemetry: Option<Arc<Telemetry>>,
 }
 
 impl ContextServerState {
     async fn new() -> Result<Self> {
+        if let Some(UnitSets(sets)) = env_config("UNIT_CONVERSION_UNIT_SETS")? {
+            units::enable_sets(sets);
+        }
+        if let Some(path) = env_config::<PathBuf>("UNIT_CONVERSION_UNITS_FILE")? {
+            units::replace_custom(units::config::load(&path)?);
+        }
+
         let resource_registry = Arc::new(ResourceRegistry::default());
 
+        for documentation in CategoryDocumentation::all() {
+            resource_registry.register(Arc::new(documentation));
+        }
+
         let prompt_registry = Arc::new(PromptRegistry::default());
 
+        prompt_registry.register(Arc::new(ConversionQuiz));
+
         let tool_registry = Arc::new(ToolRegistry::default());
 
-        tool_registry.register(Arc::new(UnitConversion));
+        let mut limits = BatchLimits::new();
+        if let Some(max_batch_items) = env_config("UNIT_CONVERSION_MAX_BATCH_ITEMS")? {
+            limits.max_batch_items = max_batch_items;
+        }
+        if let Some(max_response_bytes) = env_config("UNIT_CONVERSION_MAX_RESPONSE_BYTES")? {
+            limits.max_response_bytes = max_response_bytes;
+        }
+
+        let mut unit_conversion = UnitConversion::new().with_limits(limits);
+        let mut convert_to_all = ConvertToAll::new();
+        if let Some(system) = env_config("UNIT_CONVERSION_DEFAULT_SYSTEM")? {
+            unit_conversion = unit_conversion.with_default_system(system);
+            convert_to_all = convert_to_all.with_default_system(system);
+        }
+
+        let telemetry = env_config::<PathBuf>("UNIT_CONVERSION_TELEMETRY_FILE")?
+            .map(Telemetry::open)
+            .transpose()?
+            .map(Arc::new);
+        if let Some(telemetry) = &telemetry {
+            unit_conversion = unit_conversion.with_telemetry(telemetry.clone());
+        }
+
+        if let Some(path) = env_config::<PathBuf>("UNIT_CONVERSION_PLAUSIBILITY_FILE")? {
+            unit_conversion =
+                unit_conversion.with_plausibility(Arc::new(PlausibilityBounds::load(&path)?));
+        }
+
+        let mut list_units = ListUnits::new();
+        if let Some(dir) = env_config::<PathBuf>("UNIT_CONVERSION_PLUGINS_DIR")? {
+            let plugins = Arc::new(Plugins::discover(dir).await?);
+            unit_conversion = unit_conversion.with_plugins(plugins.clone());
+            list_units = list_units.with_plugins(plugins);
+        }
+
+        let history = Arc::new(History::new());
+        let unit_conversion = Arc::new(unit_conversion.with_history(history.clone()));
+
+        let mut annotations = HashMap::new();
+        let conversion_history = ConversionHistory::new(history, unit_conversion.clone());
+        register_tool(&tool_registry, &mut annotations, unit_conversion);
+        register_tool(
+            &tool_registry,
+            &mut annotations,
+            Arc::new(conversion_history),
+        );
+        register_tool(&tool_registry, &mut annotations, Arc::new(convert_to_all));
+        register_tool(&tool_registry, &mut annotations, Arc::new(list_units));
+        register_tool(
+            &tool_registry,
+            &mut annotations,
+            Arc::new(RackCapacity::new()),
+        );
+
+        let mut registry_diff = RegistryDiff::new(env!("CARGO_PKG_VERSION"));
+        if let Some(path) = env_config::<PathBuf>("UNIT_CONVERSION_REGISTRY_SNAPSHOT")? {
+            if let Some(previous_run) = Manifest::load(&path)? {
+                registry_diff = registry_diff.with_previous_run(previous_run);
+            }
+            Manifest::current(env!("CARGO_PKG_VERSION")).save(&path)?;
+        }
+        register_tool(&tool_registry, &mut annotations, Arc::new(registry_diff));
 
         Ok(Self {
             rpc: ContextServer::builder()
@@ -30,35 +129,268 @@ impl ContextServerState {
                 .with_resources(resource_registry)
                 .with_prompts(prompt_registry)
                 .build()?,
+            annotations,
+            telemetry,
         })
     }
 
-    async fn process_request(
-        &self,
-        request: ContextServerRpcRequest,
-    ) -> Result<Option<ContextServerRpcResponse>> {
-        self.rpc.handle_incoming_message(request).await
+    /// Writes state that is only saved periodically before the server exits.
+    async fn shutdown(&self) -> Result<()> {
+        if let Some(telemetry) = &self.telemetry {
+            telemetry.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn process_request(&self, request: ContextServerRpcRequest) -> Result<Option<Value>> {
+        let Some(response) = self.rpc.handle_incoming_message(request).await? else {
+            return Ok(None);
+        };
+
+        let mut response = serde_json::to_value(&response)?;
+        tools::annotate_tool_list(&mut response, &self.annotations);
+        Ok(Some(response))
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let state = ContextServerState::new().await?;
+/// Registers `tool` and remembers its annotations for `tools/list` responses.
+fn register_tool<T: AnnotatedTool + 'static>(
+    registry: &ToolRegistry,
+    annotations: &mut HashMap<String, ToolAnnotations>,
+    tool: Arc<T>,
+) {
+    annotations.insert(tool.to_tool().name, tool.annotations());
+    registry.register(tool);
+}
+
+/// Parses an optional configuration value from the environment.
+fn env_config<T>(name: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    match env::var(name) {
+        Ok(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|error: T::Err| error.into().context(format!("Invalid {}", name))),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Where `--debug-wire` copies every JSON-RPC frame read from stdin and written to stdout.
+enum WireLog {
+    Stderr,
+    File(File),
+}
+
+impl WireLog {
+    /// `--debug-wire` logs to stderr and `--debug-wire=<path>` appends to a file.
+    fn from_args() -> Result<Option<Self>> {
+        for arg in env::args().skip(1) {
+            if arg == "--debug-wire" {
+                return Ok(Some(Self::Stderr));
+            }
+            if let Some(path) = arg.strip_prefix("--debug-wire=") {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .with_context(|| format!("Failed to open wire log {}", path))?;
+                return Ok(Some(Self::File(file)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Records `frame` verbatim, `<-` for inbound and `->` for outbound.
+    fn record(&mut self, direction: &str, frame: &str) {
+        let entry = format!("{} {}\n", direction, frame);
+        let result = match self {
+            Self::Stderr => std::io::stderr().write_all(entry.as_bytes()),
+            Self::File(file) => file.write_all(entry.as_bytes()),
+        };
+        if let Err(error) = result {
+            diagnostics::report(format_args!("Error writing wire log: {}", error));
+        }
+    }
+}
+
+/// Ids of the latest requests on a connection, so a client that retries a request it
+/// already sent gets an error back instead of the request running twice.
+struct SeenRequestIds {
+    order: VecDeque<String>,
+    ids: HashSet<String>,
+}
+
+impl SeenRequestIds {
+    const CAPACITY: usize = 1024;
+
+    fn new() -> Self {
+        Self {
+            order: VecDeque::with_capacity(Self::CAPACITY),
+            ids: HashSet::with_capacity(Self::CAPACITY),
+        }
+    }
+
+    /// The key a request's id is tracked under, or `None` for notifications and responses,
+    /// which carry no id to track.
+    fn key(message: &Value) -> Option<String> {
+        message.get("method")?;
+        let id = message.get("id").filter(|id| !id.is_null())?;
+        // Keyed on the JSON text so the string "1" and the number 1 stay distinct ids.
+        Some(id.to_string())
+    }
+
+    /// The error response to send in place of `message` if its id was already remembered.
+    fn duplicate(&self, message: &Value) -> Option<Value> {
+        let key = Self::key(message)?;
+        if !self.ids.contains(&key) {
+            return None;
+        }
+        let id = &message["id"];
+        Some(json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+                "code": -32600,
+                "message": format!("Duplicate request id {}: a request with this id was already received and was not executed again", key),
+                "data": { "reason": "duplicate_request_id", "id": id }
+            }
+        }))
+    }
+
+    /// Remembers a request id once the request has been handled, so a request that never
+    /// parsed can be corrected and sent again under the same id.
+    fn remember(&mut self, key: String) {
+        if self.order.len() == Self::CAPACITY
+            && let Some(oldest) = self.order.pop_front()
+        {
+            self.ids.remove(&oldest);
+        }
+        self.order.push_back(key.clone());
+        self.ids.insert(key);
+    }
+}
+
+/// Hidden test modes that delay or fail a share of tool calls, so client developers can
+/// exercise their timeout and retry handling. The affected calls are spread evenly and are
+/// the same on every run.
+#[derive(Default)]
+struct FaultInjection {
+    latency: Option<(Duration, u64)>,
+    error_percent: u64,
+    tool_calls: u64,
+}
 
+impl FaultInjection {
+    /// `--inject-latency=<ms>[@<percent>]` delays that share of tool calls (all of them
+    /// without a percentage) and `--inject-errors=<percent>` fails that share.
+    fn from_args() -> Result<Self> {
+        let mut injection = Self::default();
+        for arg in env::args().skip(1) {
+            if let Some(spec) = arg.strip_prefix("--inject-latency=") {
+                let (millis, percent) = spec.split_once('@').unwrap_or((spec, "100"));
+                let millis = millis
+                    .parse()
+                    .with_context(|| format!("Invalid --inject-latency {}", spec))?;
+                injection.latency = Some((Duration::from_millis(millis), Self::percent(percent)?));
+            } else if let Some(percent) = arg.strip_prefix("--inject-errors=") {
+                injection.error_percent = Self::percent(percent)?;
+            }
+        }
+        Ok(injection)
+    }
+
+    fn percent(value: &str) -> Result<u64> {
+        match value.parse() {
+            Ok(percent) if percent <= 100 => Ok(percent),
+            _ => Err(anyhow!("Invalid percentage {}, expected 0 to 100", value)),
+        }
+    }
+
+    /// Whether the `call`th tool call, counting from 1, is among `percent` in every hundred.
+    fn picks(percent: u64, call: u64) -> bool {
+        call * percent / 100 != (call - 1) * percent / 100
+    }
+
+    /// Delays `message` if it is a tool call picked for latency, and returns the error
+    /// response to send in its place if it is picked to fail.
+    async fn apply(&mut self, message: &Value) -> Option<Value> {
+        if message.get("method").and_then(Value::as_str) != Some("tools/call") {
+            return None;
+        }
+        self.tool_calls += 1;
+
+        if let Some((delay, percent)) = self.latency
+            && Self::picks(percent, self.tool_calls)
+        {
+            tokio::time::sleep(delay).await;
+        }
+
+        Self::picks(self.error_percent, self.tool_calls).then(|| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": message["id"],
+                "error": {
+                    "code": -32603,
+                    "message": "Injected failure (--inject-errors)"
+                }
+            })
+        })
+    }
+}
+
+/// Answers requests from stdin until it closes.
+async fn serve(
+    state: &ContextServerState,
+    mut wire_log: Option<WireLog>,
+    mut fault_injection: FaultInjection,
+) -> Result<()> {
     let mut stdin = BufReader::new(io::stdin()).lines();
     let mut stdout = io::stdout();
+    let mut seen_request_ids = SeenRequestIds::new();
 
     while let Some(line) = stdin.next_line().await? {
-        let request: ContextServerRpcRequest = match serde_json::from_str(&line) {
-            Ok(req) => req,
+        if let Some(wire_log) = &mut wire_log {
+            wire_log.record("<-", &line);
+        }
+
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
             Err(e) => {
-                eprintln!("Error parsing request: {}", e);
+                diagnostics::report(format_args!("Error parsing request: {}", e));
                 continue;
             }
         };
 
-        if let Some(response) = state.process_request(request).await? {
-            let response_json = serde_json::to_string(&response)?;
+        let request_key = SeenRequestIds::key(&message);
+        let response = if let Some(duplicate) = seen_request_ids.duplicate(&message) {
+            Some(duplicate)
+        } else if let Some(fault) = fault_injection.apply(&message).await {
+            // The injected failure stands in for a transient error, so the client's retry
+            // under the same id must still run.
+            Some(fault)
+        } else {
+            let request: ContextServerRpcRequest = match serde_json::from_value(message) {
+                Ok(req) => req,
+                Err(e) => {
+                    diagnostics::report(format_args!("Error parsing request: {}", e));
+                    continue;
+                }
+            };
+            let response = state.process_request(request).await?;
+            if let Some(key) = request_key {
+                seen_request_ids.remember(key);
+            }
+            response
+        };
+
+        if let Some(response) = response {
+            let response_json = response.to_string();
+            if let Some(wire_log) = &mut wire_log {
+                wire_log.record("->", &response_json);
+            }
             stdout.write_all(response_json.as_bytes()).await?;
             stdout.write_all(b"\n").await?;
             stdout.flush().await?;
@@ -67,3 +399,92 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Resolves once the process is asked to stop, by Ctrl-C or, on Unix, SIGTERM. Without
+/// signal handling it never resolves, and the server stops when stdin closes.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    if let Ok(mut terminate) = tokio::signal::unix::signal(SignalKind::terminate()) {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {},
+            _ = terminate.recv() => {},
+        }
+        return;
+    }
+    if tokio::signal::ctrl_c().await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let wire_log = WireLog::from_args()?;
+    let fault_injection = FaultInjection::from_args()?;
+    let state = ContextServerState::new().await?;
+
+    let served = tokio::select! {
+        served = serve(&state, wire_log, fault_injection) => served,
+        () = shutdown_signal() => Ok(()),
+    };
+    // Runs however serving ended, so an I/O error or a signal keeps the latest counts.
+    let shutdown = state.shutdown().await;
+    served.and(shutdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_ids_count_as_duplicates_once_remembered() {
+        let mut seen = SeenRequestIds::new();
+        let request = json!({ "jsonrpc": "2.0", "id": 7, "method": "tools/call" });
+        let key = SeenRequestIds::key(&request).unwrap();
+
+        // Nothing is remembered while a request fails to parse or is not yet handled.
+        assert!(seen.duplicate(&request).is_none());
+        assert!(seen.duplicate(&request).is_none());
+
+        seen.remember(key);
+        let duplicate = seen.duplicate(&request).unwrap();
+        assert_eq!(duplicate["error"]["code"], json!(-32600));
+
+        let same_text = json!({ "jsonrpc": "2.0", "id": "7", "method": "tools/call" });
+        assert!(seen.duplicate(&same_text).is_none());
+    }
+
+    #[test]
+    fn notifications_and_responses_have_no_request_id() {
+        assert!(SeenRequestIds::key(&json!({ "method": "notifications/initialized" })).is_none());
+        assert!(SeenRequestIds::key(&json!({ "id": 1, "result": {} })).is_none());
+        assert!(SeenRequestIds::key(&json!({ "id": null, "method": "ping" })).is_none());
+    }
+
+    #[test]
+    fn only_the_latest_request_ids_are_remembered() {
+        let request = |id: usize| {
+            let mut request = json!({ "method": "ping" });
+            request["id"] = Value::from(id);
+            request
+        };
+        let mut seen = SeenRequestIds::new();
+        for id in 0..=SeenRequestIds::CAPACITY {
+            seen.remember(SeenRequestIds::key(&request(id)).unwrap());
+        }
+        assert!(seen.duplicate(&request(0)).is_none());
+        assert!(seen.duplicate(&request(1)).is_some());
+        assert!(seen.duplicate(&request(SeenRequestIds::CAPACITY)).is_some());
+    }
+
+    #[test]
+    fn fault_injection_picks_its_share_of_calls() {
+        for percent in [0, 33, 100] {
+            for calls in [1, 3, 99, 100, 250] {
+                let picked = (1..=calls)
+                    .filter(|call| FaultInjection::picks(percent, *call))
+                    .count() as u64;
+                assert_eq!(picked, calls * percent / 100, "{}% of {}", percent, calls);
+            }
+        }
+    }
+}